@@ -1,6 +1,11 @@
+use std::{fmt::Display, time::Duration};
+
 /// A single accumulator, represents "Akkumulator/Alpha" from SysInf lecture.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Accumulator {
     /// Used to identify accumulator
+    #[allow(dead_code)]
     pub id: i32,
     /// The data stored in the Accumulator
     pub data: Option<i32>,
@@ -18,7 +23,10 @@ impl Accumulator {
 
 /// Representation of a single memory cell.
 /// The term memory cell is equal to "Speicherzelle" in the SysInf lecture.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MemoryCell {
+    #[allow(dead_code)]
     pub label: String,
     pub data: Option<i32>,
 }
@@ -34,6 +42,9 @@ impl MemoryCell {
 }
 
 /// Different ways of paring two values
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(dead_code)]
 pub enum Comparison {
     Less,
     LessOrEqual,
@@ -65,29 +76,206 @@ impl Comparison {
     }
 }
 
+impl Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Less => write!(f, "<"),
+            Self::LessOrEqual => write!(f, "<="),
+            Self::Equal => write!(f, "="),
+            Self::MoreOrEqual => write!(f, ">="),
+            Self::More => write!(f, ">"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(dead_code)]
 pub enum Operation {
     Plus,
     Minus,
     Multiplication,
     Division,
+    Min,
+    Max,
 }
 
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Plus => write!(f, "+"),
+            Self::Minus => write!(f, "-"),
+            Self::Multiplication => write!(f, "*"),
+            Self::Division => write!(f, "/"),
+            Self::Min => write!(f, "min"),
+            Self::Max => write!(f, "max"),
+        }
+    }
+}
+
+#[allow(dead_code)]
 impl Operation {
     
-    pub fn calc(&self, x: i32, y: i32) -> i32 {
+    /// Calculates **x op y**.
+    /// 
+    /// Errors when **y** is 0 in a division or when the result does not fit into an i32, for example **i32::MIN / -1**.
+    pub fn calc(&self, x: i32, y: i32) -> Result<i32, RuntimeError> {
+        let result = match self {
+            Self::Plus => x.checked_add(y),
+            Self::Minus => x.checked_sub(y),
+            Self::Multiplication => x.checked_mul(y),
+            Self::Division if y == 0 => return Err(RuntimeError::DivisionByZero),
+            Self::Division => x.checked_div(y),
+            Self::Min => Some(x.min(y)),
+            Self::Max => Some(x.max(y)),
+        };
+        result.ok_or_else(|| RuntimeError::Overflow(format!("{} {} {}", x, self, y)))
+    }
+
+    /// Returns true when using **value** as the second operand never changes the first operand, for example **x + 0**.
+    pub fn is_identity(&self, value: i32) -> bool {
+        match self {
+            Self::Plus | Self::Minus => value == 0,
+            Self::Multiplication | Self::Division => value == 1,
+            Self::Min => value == i32::MAX,
+            Self::Max => value == i32::MIN,
+        }
+    }
+
+}
+
+/// Operations that change a single value
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(dead_code)]
+pub enum Transform {
+    /// x * x
+    Square,
+    /// x * 2
+    Double,
+    /// x / 2, rounded towards zero
+    Half,
+}
+
+impl Transform {
+    /// Applies the transform to **x**, returns None when the result does not fit into an i32.
+    pub fn apply(&self, x: i32) -> Option<i32> {
+        match self {
+            Self::Square => x.checked_mul(x),
+            Self::Double => x.checked_mul(2),
+            Self::Half => Some(x / 2),
+        }
+    }
+}
+
+impl Display for Transform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Plus => x+y,
-            Self::Minus => x-y,
-            Self::Multiplication => x*y,
-            Self::Division => x/y,
+            Self::Square => write!(f, "square"),
+            Self::Double => write!(f, "double"),
+            Self::Half => write!(f, "half"),
         }
     }
+}
 
+/// Errors that can occur while running instructions.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(dead_code)]
+pub enum RuntimeError {
+    /// The accumulator with this index does not exist
+    AccumulatorDoesNotExist(usize),
+    /// The accumulator with this index does not contain data
+    AccumulatorUninitialized(usize),
+    /// The memory cell with this label does not exist
+    MemoryCellDoesNotExist(String),
+    /// The memory cell with this label does not contain data
+    MemoryCellUninitialized(String),
+    /// No instruction index is set for this label
+    LabelMissing(String),
+    /// The stack does not contain any values
+    StackEmpty,
+    /// The stack still contains this number of values
+    StackNotEmpty(usize),
+    /// The stack contains less values than required, contains the required and the actual number of values
+    StackTooSmall(usize, usize),
+    /// The result of this calculation does not fit into an i32
+    Overflow(String),
+    /// Return was called without a matching call
+    CallStackEmpty,
+    /// A value was divided by zero
+    DivisionByZero,
+    /// The program did not finish within this duration
+    Timeout(Duration),
+    /// An assert instruction failed, contains the checked condition with the actual values
+    AssertionFailed(String),
+    /// This value can not be used as index
+    InvalidIndex(i32),
+    /// The accumulator index is above the soft limit, contains the index and the limit
+    AccumulatorAboveSoftLimit(usize, usize),
+}
+
+#[allow(dead_code)]
+impl RuntimeError {
+    /// Returns the name of the error variant.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::AccumulatorDoesNotExist(_) => "AccumulatorDoesNotExist",
+            Self::AccumulatorUninitialized(_) => "AccumulatorUninitialized",
+            Self::MemoryCellDoesNotExist(_) => "MemoryCellDoesNotExist",
+            Self::MemoryCellUninitialized(_) => "MemoryCellUninitialized",
+            Self::LabelMissing(_) => "LabelMissing",
+            Self::StackEmpty => "StackEmpty",
+            Self::StackNotEmpty(_) => "StackNotEmpty",
+            Self::StackTooSmall(_, _) => "StackTooSmall",
+            Self::Overflow(_) => "Overflow",
+            Self::CallStackEmpty => "CallStackEmpty",
+            Self::DivisionByZero => "DivisionByZero",
+            Self::Timeout(_) => "Timeout",
+            Self::AssertionFailed(_) => "AssertionFailed",
+            Self::InvalidIndex(_) => "InvalidIndex",
+            Self::AccumulatorAboveSoftLimit(_, _) => "AccumulatorAboveSoftLimit",
+        }
+    }
+
+    /// Returns the error as json object containing the error variant,
+    /// the index of the instruction that caused the error and the error message.
+    /// 
+    /// The index is returned by [step_with_index](../runtime/struct.Runner.html#method.step_with_index).
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self, instruction_index: usize) -> String {
+        serde_json::json!({
+            "variant": self.name(),
+            "instruction_index": instruction_index,
+            "message": self.to_string(),
+        }).to_string()
+    }
+}
+
+impl Display for RuntimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AccumulatorDoesNotExist(index) => write!(f, "Accumulator with index {} does not exist!", index),
+            Self::AccumulatorUninitialized(index) => write!(f, "Accumulator with index {} does not contain data!", index),
+            Self::MemoryCellDoesNotExist(label) => write!(f, "Memory cell with label {} does not exist!", label),
+            Self::MemoryCellUninitialized(label) => write!(f, "Memory cell with label {} does not contain data!", label),
+            Self::LabelMissing(label) => write!(f, "Unable to update instruction index: no index found for label {}", label),
+            Self::StackEmpty => write!(f, "Stack is empty!"),
+            Self::StackNotEmpty(len) => write!(f, "Stack is not empty, {} values are left on the stack!", len),
+            Self::StackTooSmall(required, len) => write!(f, "Stack contains {} values, but {} are required!", len, required),
+            Self::Overflow(calculation) => write!(f, "Result of {} does not fit into an i32!", calculation),
+            Self::CallStackEmpty => write!(f, "Unable to return: no call to return from!"),
+            Self::DivisionByZero => write!(f, "Unable to divide by zero!"),
+            Self::Timeout(timeout) => write!(f, "Program did not finish within {:?}!", timeout),
+            Self::AssertionFailed(condition) => write!(f, "Assertion failed: {}!", condition),
+            Self::InvalidIndex(value) => write!(f, "Value {} can not be used as index!", value),
+            Self::AccumulatorAboveSoftLimit(index, limit) => write!(f, "Accumulator with index {} is above the limit of {}!", index, limit),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::base::{Comparison, Operation};
+    use crate::base::{Comparison, Operation, RuntimeError, Transform};
 
     #[test]
     fn test_comparison() {
@@ -102,10 +290,46 @@ mod tests {
 
     #[test]
     fn test_operation() {
-        assert_eq!(Operation::Plus.calc(20, 5), 25);
-        assert_eq!(Operation::Minus.calc(20, 5), 15);
-        assert_eq!(Operation::Multiplication.calc(20, 5), 100);
-        assert_eq!(Operation::Division.calc(20, 5), 4);
+        assert_eq!(Operation::Plus.calc(20, 5), Ok(25));
+        assert_eq!(Operation::Minus.calc(20, 5), Ok(15));
+        assert_eq!(Operation::Multiplication.calc(20, 5), Ok(100));
+        assert_eq!(Operation::Division.calc(20, 5), Ok(4));
+    }
+
+    #[test]
+    fn test_operation_errors() {
+        assert_eq!(Operation::Division.calc(20, 0), Err(RuntimeError::DivisionByZero));
+        assert_eq!(Operation::Division.calc(i32::MIN, -1), Err(RuntimeError::Overflow(format!("{} / -1", i32::MIN))));
+        assert_eq!(Operation::Plus.calc(i32::MAX, 1), Err(RuntimeError::Overflow(format!("{} + 1", i32::MAX))));
+        assert_eq!(Operation::Minus.calc(i32::MIN, 1), Err(RuntimeError::Overflow(format!("{} - 1", i32::MIN))));
+        assert_eq!(Operation::Multiplication.calc(i32::MAX, 2), Err(RuntimeError::Overflow(format!("{} * 2", i32::MAX))));
+    }
+
+    #[test]
+    fn test_operation_min_max() {
+        assert_eq!(Operation::Min.calc(-3, 5), Ok(-3));
+        assert_eq!(Operation::Min.calc(5, -3), Ok(-3));
+        assert_eq!(Operation::Max.calc(-3, 5), Ok(5));
+        assert_eq!(Operation::Max.calc(5, -3), Ok(5));
+        assert_eq!(Operation::Max.calc(-7, 0), Ok(0));
+    }
+
+    #[test]
+    fn test_operation_is_identity() {
+        assert!(Operation::Plus.is_identity(0));
+        assert!(!Operation::Minus.is_identity(1));
+        assert!(Operation::Multiplication.is_identity(1));
+        assert!(!Operation::Division.is_identity(0));
+        assert!(Operation::Min.is_identity(i32::MAX));
+        assert!(Operation::Max.is_identity(i32::MIN));
     }
 
+    #[test]
+    fn test_transform() {
+        assert_eq!(Transform::Square.apply(-4), Some(16));
+        assert_eq!(Transform::Double.apply(7), Some(14));
+        assert_eq!(Transform::Half.apply(7), Some(3));
+        assert_eq!(Transform::Half.apply(-7), Some(-3));
+        assert_eq!(Transform::Square.apply(i32::MAX), None);
+    }
 }
\ No newline at end of file
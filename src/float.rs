@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use crate::{base::{Comparison, Operation, RuntimeError}, ACCUMULATORS, MEMORY_CELL_LABELS};
+
+#[allow(dead_code)]
+impl Operation {
+    /// Calculates the result of the operation with floating point semantics.
+    /// 
+    /// Division by zero results in infinity or NaN instead of an error.
+    pub fn calc_float(&self, x: f64, y: f64) -> f64 {
+        match self {
+            Self::Plus => x+y,
+            Self::Minus => x-y,
+            Self::Multiplication => x*y,
+            Self::Division => x/y,
+            Self::Min => x.min(y),
+            Self::Max => x.max(y),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl Comparison {
+    /// Compares two floating point values with the selected method of comparison.
+    /// 
+    /// Every comparison that involves NaN is false.
+    pub fn cmp_float(&self, x: f64, y: f64) -> bool {
+        match self {
+            Self::Less => x < y,
+            Self::LessOrEqual => x <= y,
+            Self::Equal => x == y,
+            Self::MoreOrEqual => x >= y,
+            Self::More => x > y,
+        }
+    }
+}
+
+/// Floating point counterpart of [RuntimeArgs](../runtime/struct.RuntimeArgs.html).
+#[derive(Clone)]
+#[allow(dead_code)]
+pub struct FloatRuntimeArgs<'a> {
+    /// Current values stored in accumulators
+    pub accumulators: Vec<Option<f64>>,
+    /// All registers that are used to store data
+    pub memory_cells: HashMap<&'a str, Option<f64>>,
+    /// The stack of the runner
+    pub stack: Vec<f64>,
+}
+
+#[allow(dead_code)]
+impl<'a> FloatRuntimeArgs<'a> {
+    pub fn new() -> Self {
+        let mut memory_cells = HashMap::new();
+        for i in MEMORY_CELL_LABELS {
+            memory_cells.insert(*i, None);
+        }
+        Self {
+            accumulators: vec![None; ACCUMULATORS.max(1) as usize],
+            memory_cells,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Sets the value of the accumulator with index **index**.
+    pub fn set_accumulator(&mut self, index: usize, value: f64) -> Result<(), RuntimeError> {
+        let accumulator = self.accumulators.get_mut(index).ok_or(RuntimeError::AccumulatorDoesNotExist(index))?;
+        *accumulator = Some(value);
+        Ok(())
+    }
+
+    /// Returns the value of the accumulator with index **index**.
+    pub fn accumulator(&self, index: usize) -> Result<f64, RuntimeError> {
+        self.accumulators.get(index)
+            .ok_or(RuntimeError::AccumulatorDoesNotExist(index))?
+            .ok_or(RuntimeError::AccumulatorUninitialized(index))
+    }
+
+    /// Runs code equal to **a := a op x**
+    /// 
+    /// - a = value of accumulator with index **a_idx**
+    /// - x = constant with value **value**
+    /// - op = the operation to perform
+    pub fn calc_accumulator_with_constant(&mut self, operation: &Operation, a_idx: usize, value: f64) -> Result<(), RuntimeError> {
+        let a = self.accumulator(a_idx)?;
+        self.set_accumulator(a_idx, operation.calc_float(a, value))
+    }
+
+    /// Runs code equal to **a := b op c**
+    /// 
+    /// - a = value of accumulator with index **a_idx_a**
+    /// - b = value of accumulator with index **a_idx_b**
+    /// - c = value of accumulator with index **a_idx_c**
+    /// - op = the operation to perform
+    pub fn calc_accumulator_with_accumulators(&mut self, operation: &Operation, a_idx_a: usize, a_idx_b: usize, a_idx_c: usize) -> Result<(), RuntimeError> {
+        let b = self.accumulator(a_idx_b)?;
+        let c = self.accumulator(a_idx_c)?;
+        self.set_accumulator(a_idx_a, operation.calc_float(b, c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{base::{Comparison, Operation, RuntimeError}, float::FloatRuntimeArgs};
+
+    #[test]
+    fn test_operation_float() {
+        assert_eq!(Operation::Division.calc_float(1.0, 4.0), 0.25);
+        assert_eq!(Operation::Min.calc_float(-0.5, 0.5), -0.5);
+        assert!(Operation::Division.calc_float(0.0, 0.0).is_nan());
+    }
+
+    #[test]
+    fn test_comparison_float() {
+        assert!(Comparison::Less.cmp_float(0.25, 0.5));
+        assert!(Comparison::Equal.cmp_float(0.5, 0.5));
+        assert!(!Comparison::Equal.cmp_float(f64::NAN, f64::NAN));
+        assert!(!Comparison::LessOrEqual.cmp_float(f64::NAN, 1.0));
+        assert!(!Comparison::More.cmp_float(1.0, f64::NAN));
+    }
+
+    #[test]
+    fn test_float_runtime_args() {
+        let mut args = FloatRuntimeArgs::new();
+        assert_eq!(args.calc_accumulator_with_constant(&Operation::Division, 0, 2.0), Err(RuntimeError::AccumulatorUninitialized(0)));
+        args.set_accumulator(0, 7.0).unwrap();
+        args.set_accumulator(1, 2.0).unwrap();
+        args.calc_accumulator_with_accumulators(&Operation::Division, 2, 0, 1).unwrap();
+        assert_eq!(args.accumulator(2).unwrap(), 3.5);
+        args.calc_accumulator_with_constant(&Operation::Division, 2, 2.0).unwrap();
+        assert_eq!(args.accumulator(2).unwrap(), 1.75);
+    }
+}
@@ -1,26 +1,107 @@
-use crate::{runtime::{RuntimeArgs, ControlFlow}, base::{Comparison, Operation}};
+use std::fmt::Display;
 
+use crate::{runtime::{format_value, RuntimeArgs, ControlFlow}, base::{Comparison, Operation, RuntimeError, Transform}};
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(dead_code)]
 pub enum Instruction<'a> {
     /// push
     /// 
     /// See [push](fn.push.html)
     Push(),
+    /// push x
+    /// 
+    /// See [push_constant](fn.push_constant.html)
+    PushConstant(i32),
+    /// push p(i)
+    /// 
+    /// See [push_memory_cell](fn.push_memory_cell.html)
+    PushMemoryCell(&'a str),
+    /// push a0, ..., push a(n-1)
+    /// 
+    /// See [push_all_accumulators](fn.push_all_accumulators.html)
+    PushAllAccumulators(),
+    /// if a cmp x then push a
+    /// 
+    /// See [push_if](fn.push_if.html)
+    PushIf(Comparison, usize, i32),
+    /// pop a(n-1), ..., pop a0
+    /// 
+    /// See [pop_all_accumulators](fn.pop_all_accumulators.html)
+    PopAllAccumulators(),
     /// pop
     /// 
     /// See [pop](fn.pop.html)
     Pop(),
+    /// pop n values
+    /// 
+    /// See [pop_n](fn.pop_n.html)
+    PopN(usize),
+    /// p(i) := pop
+    /// 
+    /// See [pop_memory_cell](fn.pop_memory_cell.html)
+    PopMemoryCell(&'a str),
+    /// s := -s
+    /// 
+    /// See [stack_negate](fn.stack_negate.html)
+    StackNegate(),
+    /// assert stack is empty
+    /// 
+    /// See [assert_stack_empty](fn.assert_stack_empty.html)
+    AssertStackEmpty(),
+    /// assert a = b
+    /// 
+    /// See [assert_accumulators_equal](fn.assert_accumulators_equal.html)
+    AssertAccumulatorsEqual(usize, usize),
+    /// a := stack length
+    /// 
+    /// See [stack_len](fn.stack_len.html)
+    StackLen(usize),
+    /// a := stack length - x
+    /// 
+    /// See [stack_len_minus](fn.stack_len_minus.html)
+    StackLenMinus(usize, i32),
+    /// p(base0) := s0, ..., p(base(n-1)) := s(n-1)
+    /// 
+    /// See [spill_stack](fn.spill_stack.html)
+    SpillStack(&'a str),
+    /// rotate the top n values of the stack
+    /// 
+    /// See [stack_rotate](fn.stack_rotate.html)
+    StackRotate(usize),
     /// a := x
     /// 
     /// See [assign_accumulator_value](fn.assign_accumulator_value.html)
     AssignAccumulatorValue(usize, i32),
+    /// a := if b cmp x then y else z
+    /// 
+    /// See [assign_if](fn.assign_if.html)
+    AssignIf(usize, Comparison, usize, i32, i32, i32),
+    /// p(base0) := a0, ..., p(base(n-1)) := a(n-1)
+    /// 
+    /// See [store_accumulators](fn.store_accumulators.html)
+    StoreAccumulators(usize, &'a str),
     /// a := b
     /// 
     /// See [assign_accumulator_value_from_accumulator](fn.assign_accumulator_value_from_accumulator.html)
     AssignAccumulatorValueFromAccumulator(usize, usize),
+    /// a := b, b := None
+    /// 
+    /// See [move_accumulator](fn.move_accumulator.html)
+    MoveAccumulator(usize, usize),
+    /// a := a(b)
+    /// 
+    /// See [assign_accumulator_from_indexed](fn.assign_accumulator_from_indexed.html)
+    AssignAccumulatorFromIndexed(usize, usize),
     /// a := p(i)
     /// 
     /// See [assign_accumulator_value_from_memory_cell](fn.assign_accumulator_value_from_memory_cell.html)
     AssignAccumulatorValueFromMemoryCell(usize, &'a str),
+    /// a :=: p(i)
+    /// 
+    /// See [swap_accumulator_memory_cell](fn.swap_accumulator_memory_cell.html)
+    SwapAccumulatorMemoryCell(usize, &'a str),
     /// p(i) := x
     /// 
     /// See [assign_memory_cell_value](fn.assign_memory_cell_value.html)
@@ -33,6 +114,14 @@ pub enum Instruction<'a> {
     /// 
     /// See [assign_memory_cell_value_from_memory_cell](fn.assign_memory_cell_value_from_memory_cell.html)
     AssingMemoryCellValueFromMemoryCell(&'a str, &'a str),
+    /// p(i) := s
+    /// 
+    /// See [store_stack_top](fn.store_stack_top.html)
+    StoreStackTop(&'a str),
+    /// if p(i) cmp x then p(j) := None
+    /// 
+    /// See [clear_memory_cell_if](fn.clear_memory_cell_if.html)
+    ClearMemoryCellIf(Comparison, &'a str, i32, &'a str),
     /// a := a op x
     /// 
     /// See [calc_accumulator_with_constant](fn.calc_accumulator_with_constant.html)
@@ -53,6 +142,10 @@ pub enum Instruction<'a> {
     /// 
     /// See [calc_accumulator_with_memory_cells](fn.calc_accumulator_with_memory_cells.html)
     CalcAccumulatorWithMemoryCells(Operation, usize, &'a str, &'a str),
+    /// a := a op s
+    /// 
+    /// See [calc_accumulator_with_stack_top](fn.calc_accumulator_with_stack_top.html)
+    CalcAccumulatorWithStackTop(Operation, usize),
     /// p(i) := p(j) op x
     /// 
     /// See [calc_memory_cell_with_memory_cell_constant](fn.calc_memory_cell_with_memory_cell_constant.html)
@@ -65,6 +158,46 @@ pub enum Instruction<'a> {
     /// 
     /// See [calc_memory_cell_with_memory_cells](fn.calc_memory_cell_with_memory_cells.html)
     CalcMemoryCellWithMemoryCells(Operation, &'a str, &'a str, &'a str),
+    /// a := a + p(i) * p(j)
+    /// 
+    /// See [mul_add](fn.mul_add.html)
+    MulAdd(usize, &'a str, &'a str),
+    /// a := |b - c|
+    /// 
+    /// See [abs_diff](fn.abs_diff.html)
+    AbsDiff(usize, usize, usize),
+    /// a := f(a)
+    /// 
+    /// See [transform_accumulator](fn.transform_accumulator.html)
+    TransformAccumulator(usize, Transform),
+    /// a := gcd(b, c)
+    /// 
+    /// See [gcd](fn.gcd.html)
+    Gcd(usize, usize, usize),
+    /// a := popcount(b)
+    /// 
+    /// See [pop_count](fn.pop_count.html)
+    PopCount(usize, usize),
+    /// a := min(p(base0), ..., p(base(n-1)))
+    /// 
+    /// See [min_of_cells](fn.min_of_cells.html)
+    MinOfCells(usize, &'a str, usize),
+    /// a := max(p(base0), ..., p(base(n-1)))
+    /// 
+    /// See [max_of_cells](fn.max_of_cells.html)
+    MaxOfCells(usize, &'a str, usize),
+    /// a := p(base0) + ... + p(base(n-1))
+    /// 
+    /// See [sum_cells](fn.sum_cells.html)
+    SumCells(usize, &'a str, usize),
+    /// a := a rotl n
+    /// 
+    /// See [rotate_left](fn.rotate_left.html)
+    RotateLeft(usize, u32),
+    /// a := a rotr n
+    /// 
+    /// See [rotate_right](fn.rotate_right.html)
+    RotateRight(usize, u32),
     /// goto label
     /// 
     /// See [ControlFlow](../runtime/struct.ControlFlow.html) and [goto](fn.goto.html) for further information.
@@ -81,78 +214,857 @@ pub enum Instruction<'a> {
     /// 
     /// See [goto_if_memory_cell](fn.goto_if_memory_cell.html)
     GotoIfMemoryCell(Comparison, &'a str, usize, &'a str),
+    /// if p(i) cmp x then goto label
+    /// 
+    /// See [goto_if_memory_cell_constant](fn.goto_if_memory_cell_constant.html)
+    GotoIfMemoryCellConstant(Comparison, &'a str, &'a str, i32),
+    /// if a % x == 0 then goto label
+    /// 
+    /// See [goto_if_divisible](fn.goto_if_divisible.html)
+    GotoIfDivisible(usize, i32, &'a str),
+    /// if pop cmp x then goto label
+    /// 
+    /// See [goto_if_stack_pop](fn.goto_if_stack_pop.html)
+    GotoIfStackPop(Comparison, &'a str, i32),
+    /// if s cmp p(i) then goto label
+    /// 
+    /// See [goto_if_stack_top_memory_cell](fn.goto_if_stack_top_memory_cell.html)
+    GotoIfStackTopMemoryCell(Comparison, &'a str, &'a str),
+    /// a := address of label
+    /// 
+    /// See [load_label_address](fn.load_label_address.html)
+    LoadLabelAddress(usize, &'a str),
+    /// goto a
+    /// 
+    /// See [goto_accumulator](fn.goto_accumulator.html)
+    GotoAccumulator(usize),
+    /// call label
+    /// 
+    /// See [call](fn.call.html)
+    Call(&'a str),
+    /// return
+    /// 
+    /// See [return_from_call](fn.return_from_call.html)
+    Return(),
+    /// Clears all accumulators, memory cells and the stack
+    /// 
+    /// See [reset_state](fn.reset_state.html)
+    ResetState(),
+    /// Does nothing
+    Nop(),
     /// See [print_accumulators](fn.print_accumulators.html)
     PrintAccumulators(),
     /// See [print_memory_cells](fn.print_memory_cells.html)
     PrintMemoryCells(),
     /// See [print_stack](fn.print_stack.html)
     PrintStack(),
+    /// See [print_labeled](fn.print_labeled.html)
+    PrintLabeled(&'a str, usize),
 }
 
+#[allow(dead_code)]
 impl<'a> Instruction<'a> {
-    /// Runs the instruction, retuns Err(String) when instruction could not be ran.
+    /// Runs the instruction, retuns Err(RuntimeError) when instruction could not be ran.
     /// Err contains the reason why running the instruction failed.
-    pub fn run(&self, runtime_args: &mut RuntimeArgs<'a>, control_flow: &mut ControlFlow<'a>) -> Result<(), String> {
+    pub fn run(&self, runtime_args: &mut RuntimeArgs<'a>, control_flow: &mut ControlFlow<'a>) -> Result<(), RuntimeError> {
+        self.run_linked(runtime_args, control_flow, None)
+    }
+
+    /// Runs the instruction like [run](#method.run), a jump to the label of the instruction goes to **linked_index** when it is set,
+    /// instead of looking up the label.
+    /// 
+    /// See [link](../runtime/struct.Runner.html#method.link)
+    pub(crate) fn run_linked(&self, runtime_args: &mut RuntimeArgs<'a>, control_flow: &mut ControlFlow<'a>, linked_index: Option<usize>) -> Result<(), RuntimeError> {
         match self {
             Self::Push() => push(runtime_args)?,
+            Self::PushConstant(value) => push_constant(runtime_args, value),
+            Self::PushMemoryCell(label) => push_memory_cell(runtime_args, label)?,
+            Self::PushAllAccumulators() => push_all_accumulators(runtime_args),
+            Self::PushIf(comparison, a_idx, value) => push_if(runtime_args, comparison, a_idx, value)?,
+            Self::PopAllAccumulators() => pop_all_accumulators(runtime_args)?,
             Self::Pop() => pop(runtime_args)?,
+            Self::PopN(n) => pop_n(runtime_args, n)?,
+            Self::PopMemoryCell(label) => pop_memory_cell(runtime_args, label)?,
+            Self::StackNegate() => stack_negate(runtime_args)?,
+            Self::AssertStackEmpty() => assert_stack_empty(runtime_args)?,
+            Self::AssertAccumulatorsEqual(a_idx_a, a_idx_b) => assert_accumulators_equal(runtime_args, a_idx_a, a_idx_b)?,
+            Self::StackLen(a_idx) => stack_len(runtime_args, a_idx)?,
+            Self::StackLenMinus(a_idx, value) => stack_len_minus(runtime_args, a_idx, value)?,
+            Self::SpillStack(base_label) => spill_stack(runtime_args, base_label)?,
+            Self::StackRotate(n) => stack_rotate(runtime_args, n)?,
             Self::AssignAccumulatorValue(a_idx, value) => assign_accumulator_value(runtime_args, a_idx, value)?,
+            Self::AssignIf(a_idx_a, comparison, a_idx_b, value, if_true, if_false) => assign_if(runtime_args, a_idx_a, comparison, a_idx_b, value, if_true, if_false)?,
+            Self::StoreAccumulators(count, base_label) => store_accumulators(runtime_args, count, base_label)?,
             Self::AssignAccumulatorValueFromAccumulator(a_idx_a, a_idx_b) => assign_accumulator_value_from_accumulator(runtime_args, a_idx_a, a_idx_b)?,
+            Self::MoveAccumulator(a_idx_a, a_idx_b) => move_accumulator(runtime_args, a_idx_a, a_idx_b)?,
+            Self::AssignAccumulatorFromIndexed(a_idx_a, a_idx_b) => assign_accumulator_from_indexed(runtime_args, a_idx_a, a_idx_b)?,
             Self::AssignAccumulatorValueFromMemoryCell(a_idx, label) => assign_accumulator_value_from_memory_cell(runtime_args, a_idx, label)?,
+            Self::SwapAccumulatorMemoryCell(a_idx, label) => swap_accumulator_memory_cell(runtime_args, a_idx, label)?,
             Self::AssignMemoryCellValue(label, value) => assign_memory_cell_value(runtime_args, label, value)?,
             Self::AssignMemoryCellValueFromAccumulator(label, a_idx) => assign_memory_cell_value_from_accumulator(runtime_args, label, a_idx)?,
             Self::AssingMemoryCellValueFromMemoryCell(label_a, label_b) => assign_memory_cell_value_from_memory_cell(runtime_args, label_a, label_b)?,
+            Self::StoreStackTop(label) => store_stack_top(runtime_args, label)?,
+            Self::ClearMemoryCellIf(comparison, label_a, value, label_b) => clear_memory_cell_if(runtime_args, comparison, label_a, value, label_b)?,
             Self::CalcAccumulatorWithConstant(operation, a_idx, value) => calc_accumulator_with_constant(runtime_args, operation, a_idx, value)?,
             Self::CalcAccumulatorWithAccumulator(operation, a_idx_a, a_idx_b) => calc_accumulator_with_accumulator(runtime_args, operation, a_idx_a, a_idx_b)?,
             Self::CalcAccumulatorWithAccumulators(operation, a_idx_a, a_idx_b, a_idx_c) => calc_accumulator_with_accumulators(runtime_args, operation, a_idx_a, a_idx_b, a_idx_c)?,
             Self::CalcAccumulatorWithMemoryCell(operation, a_idx, label) => calc_accumulator_with_memory_cell(runtime_args, operation, a_idx, label)?,
             Self::CalcAccumulatorWithMemoryCells(operation, a_idx, label_a, label_b) => calc_accumulator_with_memory_cells(runtime_args, operation, a_idx, label_a, label_b)?,
+            Self::CalcAccumulatorWithStackTop(operation, a_idx) => calc_accumulator_with_stack_top(runtime_args, operation, a_idx)?,
             Self::CalcMemoryCellWithMemoryCellAccumulator(operation, label_a, label_b, a_idx) => calc_memory_cell_with_memory_cell_accumulator(runtime_args, operation, label_a, label_b, a_idx)?,
             Self::CalcMemoryCellWithMemoryCellConstant(operation, label_a, label_b, value) => calc_memory_cell_with_memory_cell_constant(runtime_args, operation, label_a, label_b, value)?,
             Self::CalcMemoryCellWithMemoryCells(operation, label_a, label_b, label_c) => calc_memory_cell_with_memory_cells(runtime_args, operation, label_a, label_b, label_c)?,
-            Self::Goto(label) => goto(runtime_args, control_flow, label)?,
-            Self::GotoIfAccumulator(comparison, label, a_idx_a, a_idx_b) => goto_if_accumulator(runtime_args, control_flow, comparison, label, a_idx_a, a_idx_b)?,
-            Self::GotoIfConstant(comparison, label, a_idx, c) => goto_if_constant(runtime_args, control_flow, comparison, label, a_idx, c)?,
-            Self::GotoIfMemoryCell(comparison, label, a_idx, mcl) => goto_if_memory_cell(runtime_args, control_flow, comparison, label, a_idx, mcl)?,
+            Self::MulAdd(a_idx, label_a, label_b) => mul_add(runtime_args, a_idx, label_a, label_b)?,
+            Self::AbsDiff(a_idx_a, a_idx_b, a_idx_c) => abs_diff(runtime_args, a_idx_a, a_idx_b, a_idx_c)?,
+            Self::TransformAccumulator(a_idx, transform) => transform_accumulator(runtime_args, a_idx, transform)?,
+            Self::Gcd(a_idx_a, a_idx_b, a_idx_c) => gcd(runtime_args, a_idx_a, a_idx_b, a_idx_c)?,
+            Self::PopCount(a_idx_a, a_idx_b) => pop_count(runtime_args, a_idx_a, a_idx_b)?,
+            Self::MinOfCells(a_idx, base_label, count) => min_of_cells(runtime_args, a_idx, base_label, count)?,
+            Self::MaxOfCells(a_idx, base_label, count) => max_of_cells(runtime_args, a_idx, base_label, count)?,
+            Self::SumCells(a_idx, base_label, count) => sum_cells(runtime_args, a_idx, base_label, count)?,
+            Self::RotateLeft(a_idx, n) => rotate_left(runtime_args, a_idx, n)?,
+            Self::RotateRight(a_idx, n) => rotate_right(runtime_args, a_idx, n)?,
+            Self::Goto(label) => goto(runtime_args, control_flow, label, linked_index)?,
+            Self::GotoIfAccumulator(comparison, label, a_idx_a, a_idx_b) => goto_if_accumulator(runtime_args, control_flow, comparison, label, a_idx_a, a_idx_b, linked_index)?,
+            Self::GotoIfConstant(comparison, label, a_idx, c) => goto_if_constant(runtime_args, control_flow, comparison, label, a_idx, c, linked_index)?,
+            Self::GotoIfMemoryCell(comparison, label, a_idx, mcl) => goto_if_memory_cell(runtime_args, control_flow, comparison, label, a_idx, mcl, linked_index)?,
+            Self::GotoIfMemoryCellConstant(comparison, label, mcl, c) => goto_if_memory_cell_constant(runtime_args, control_flow, comparison, label, mcl, c, linked_index)?,
+            Self::GotoIfDivisible(a_idx, divisor, label) => goto_if_divisible(runtime_args, control_flow, a_idx, divisor, label, linked_index)?,
+            Self::GotoIfStackPop(comparison, label, c) => goto_if_stack_pop(runtime_args, control_flow, comparison, label, c, linked_index)?,
+            Self::GotoIfStackTopMemoryCell(comparison, label, mcl) => goto_if_stack_top_memory_cell(runtime_args, control_flow, comparison, label, mcl, linked_index)?,
+            Self::LoadLabelAddress(a_idx, label) => load_label_address(runtime_args, control_flow, a_idx, label)?,
+            Self::GotoAccumulator(a_idx) => goto_accumulator(runtime_args, control_flow, a_idx)?,
+            Self::Call(label) => call(control_flow, label, linked_index)?,
+            Self::Return() => return_from_call(control_flow)?,
+            Self::ResetState() => reset_state(runtime_args),
+            Self::Nop() => (),
             Self::PrintAccumulators() => print_accumulators(runtime_args),
             Self::PrintMemoryCells() => print_memory_cells(runtime_args),
             Self::PrintStack() => print_stack(runtime_args),
+            Self::PrintLabeled(message, a_idx) => print_labeled(runtime_args, message, a_idx)?,
         }
         Ok(())
     }
+
+    /// Returns the accumulators and memory cells this instruction reads from and writes to.
+    /// 
+    /// Note: The print instructions are not considered to read anything.
+    pub fn effects(&self) -> Effects {
+        let mut effects = Effects::default();
+        match self {
+            Self::Push() => effects.read_accumulators.push(0),
+            Self::PushConstant(_) => (),
+            Self::PushMemoryCell(label) => effects.read_memory_cells.push(label.to_string()),
+            // all accumulators are accessed, the number of accumulators is only known at runtime
+            Self::PushAllAccumulators() => (),
+            Self::PushIf(_, a_idx, _) => effects.read_accumulators.push(*a_idx),
+            Self::PopAllAccumulators() => (),
+            Self::Pop() => effects.written_accumulators.push(0),
+            Self::PopN(_) => (),
+            Self::PopMemoryCell(label) => effects.written_memory_cells.push(label.to_string()),
+            Self::StackNegate() => (),
+            Self::AssertStackEmpty() => (),
+            Self::AssertAccumulatorsEqual(a_idx_a, a_idx_b) => effects.read_accumulators.extend([*a_idx_a, *a_idx_b]),
+            Self::StackLen(a_idx) => effects.written_accumulators.push(*a_idx),
+            Self::StackLenMinus(a_idx, _) => effects.written_accumulators.push(*a_idx),
+            // the written memory cells depend on the stack size
+            Self::SpillStack(_) => (),
+            Self::StackRotate(_) => (),
+            Self::AssignAccumulatorValue(a_idx, _) => effects.written_accumulators.push(*a_idx),
+            Self::AssignIf(a_idx_a, _, a_idx_b, _, _, _) => {
+                effects.read_accumulators.push(*a_idx_b);
+                effects.written_accumulators.push(*a_idx_a);
+            },
+            Self::StoreAccumulators(count, base_label) => {
+                effects.read_accumulators.extend(0..*count);
+                effects.written_memory_cells.extend((0..*count).map(|i| format!("{}{}", base_label, i)));
+            },
+            Self::AssignAccumulatorValueFromAccumulator(a_idx_a, a_idx_b) => {
+                effects.written_accumulators.push(*a_idx_a);
+                effects.read_accumulators.push(*a_idx_b);
+            },
+            Self::MoveAccumulator(a_idx_a, a_idx_b) => {
+                effects.written_accumulators.extend([*a_idx_a, *a_idx_b]);
+                effects.read_accumulators.push(*a_idx_b);
+            },
+            // the accumulator that is read depends on the value of a_idx_b
+            Self::AssignAccumulatorFromIndexed(a_idx_a, a_idx_b) => {
+                effects.written_accumulators.push(*a_idx_a);
+                effects.read_accumulators.push(*a_idx_b);
+            },
+            Self::AssignAccumulatorValueFromMemoryCell(a_idx, label) => {
+                effects.written_accumulators.push(*a_idx);
+                effects.read_memory_cells.push(label.to_string());
+            },
+            Self::SwapAccumulatorMemoryCell(a_idx, label) => {
+                effects.read_accumulators.push(*a_idx);
+                effects.written_accumulators.push(*a_idx);
+                effects.read_memory_cells.push(label.to_string());
+                effects.written_memory_cells.push(label.to_string());
+            },
+            Self::AssignMemoryCellValue(label, _) => effects.written_memory_cells.push(label.to_string()),
+            Self::AssignMemoryCellValueFromAccumulator(label, a_idx) => {
+                effects.written_memory_cells.push(label.to_string());
+                effects.read_accumulators.push(*a_idx);
+            },
+            Self::AssingMemoryCellValueFromMemoryCell(label_a, label_b) => {
+                effects.written_memory_cells.push(label_a.to_string());
+                effects.read_memory_cells.push(label_b.to_string());
+            },
+            Self::StoreStackTop(label) => effects.written_memory_cells.push(label.to_string()),
+            Self::ClearMemoryCellIf(_, label_a, _, label_b) => {
+                effects.read_memory_cells.push(label_a.to_string());
+                effects.written_memory_cells.push(label_b.to_string());
+            },
+            Self::CalcAccumulatorWithConstant(_, a_idx, _) => {
+                effects.read_accumulators.push(*a_idx);
+                effects.written_accumulators.push(*a_idx);
+            },
+            Self::CalcAccumulatorWithAccumulator(_, a_idx_a, a_idx_b) => {
+                effects.read_accumulators.extend([*a_idx_a, *a_idx_b]);
+                effects.written_accumulators.push(*a_idx_a);
+            },
+            Self::CalcAccumulatorWithAccumulators(_, a_idx_a, a_idx_b, a_idx_c) => {
+                effects.read_accumulators.extend([*a_idx_b, *a_idx_c]);
+                effects.written_accumulators.push(*a_idx_a);
+            },
+            Self::CalcAccumulatorWithMemoryCell(_, a_idx, label) => {
+                effects.read_accumulators.push(*a_idx);
+                effects.read_memory_cells.push(label.to_string());
+                effects.written_accumulators.push(*a_idx);
+            },
+            Self::CalcAccumulatorWithMemoryCells(_, a_idx, label_a, label_b) => {
+                effects.read_memory_cells.extend([label_a.to_string(), label_b.to_string()]);
+                effects.written_accumulators.push(*a_idx);
+            },
+            Self::CalcAccumulatorWithStackTop(_, a_idx) => {
+                effects.read_accumulators.push(*a_idx);
+                effects.written_accumulators.push(*a_idx);
+            },
+            Self::CalcMemoryCellWithMemoryCellConstant(_, label_a, label_b, _) => {
+                effects.read_memory_cells.push(label_b.to_string());
+                effects.written_memory_cells.push(label_a.to_string());
+            },
+            Self::CalcMemoryCellWithMemoryCellAccumulator(_, label_a, label_b, a_idx) => {
+                effects.read_memory_cells.push(label_b.to_string());
+                effects.read_accumulators.push(*a_idx);
+                effects.written_memory_cells.push(label_a.to_string());
+            },
+            Self::CalcMemoryCellWithMemoryCells(_, label_a, label_b, label_c) => {
+                effects.read_memory_cells.extend([label_b.to_string(), label_c.to_string()]);
+                effects.written_memory_cells.push(label_a.to_string());
+            },
+            Self::MulAdd(a_idx, label_a, label_b) => {
+                effects.read_accumulators.push(*a_idx);
+                effects.read_memory_cells.extend([label_a.to_string(), label_b.to_string()]);
+                effects.written_accumulators.push(*a_idx);
+            },
+            Self::AbsDiff(a_idx_a, a_idx_b, a_idx_c) => {
+                effects.read_accumulators.extend([*a_idx_b, *a_idx_c]);
+                effects.written_accumulators.push(*a_idx_a);
+            },
+            Self::TransformAccumulator(a_idx, _) => {
+                effects.read_accumulators.push(*a_idx);
+                effects.written_accumulators.push(*a_idx);
+            },
+            Self::Gcd(a_idx_a, a_idx_b, a_idx_c) => {
+                effects.read_accumulators.extend([*a_idx_b, *a_idx_c]);
+                effects.written_accumulators.push(*a_idx_a);
+            },
+            Self::PopCount(a_idx_a, a_idx_b) => {
+                effects.read_accumulators.push(*a_idx_b);
+                effects.written_accumulators.push(*a_idx_a);
+            },
+            Self::MinOfCells(a_idx, base_label, count) | Self::MaxOfCells(a_idx, base_label, count) | Self::SumCells(a_idx, base_label, count) => {
+                effects.read_memory_cells.extend(memory_cell_range_labels(base_label, *count));
+                effects.written_accumulators.push(*a_idx);
+            },
+            Self::RotateLeft(a_idx, _) | Self::RotateRight(a_idx, _) => {
+                effects.read_accumulators.push(*a_idx);
+                effects.written_accumulators.push(*a_idx);
+            },
+            Self::Goto(_) => (),
+            Self::GotoIfAccumulator(_, _, a_idx_a, a_idx_b) => effects.read_accumulators.extend([*a_idx_a, *a_idx_b]),
+            Self::GotoIfConstant(_, _, a_idx, _) => effects.read_accumulators.push(*a_idx),
+            Self::GotoIfMemoryCell(_, _, a_idx, label) => {
+                effects.read_accumulators.push(*a_idx);
+                effects.read_memory_cells.push(label.to_string());
+            },
+            Self::GotoIfMemoryCellConstant(_, _, label, _) => effects.read_memory_cells.push(label.to_string()),
+            Self::GotoIfDivisible(a_idx, _, _) => effects.read_accumulators.push(*a_idx),
+            Self::GotoIfStackPop(_, _, _) => (),
+            Self::GotoIfStackTopMemoryCell(_, _, label) => effects.read_memory_cells.push(label.to_string()),
+            Self::LoadLabelAddress(a_idx, _) => effects.written_accumulators.push(*a_idx),
+            Self::GotoAccumulator(a_idx) => effects.read_accumulators.push(*a_idx),
+            Self::Call(_) => (),
+            Self::Return() => (),
+            // all accumulators and memory cells are cleared, they are only known at runtime
+            Self::ResetState() => (),
+            Self::Nop() => (),
+            Self::PrintAccumulators() => (),
+            Self::PrintMemoryCells() => (),
+            Self::PrintStack() => (),
+            Self::PrintLabeled(_, a_idx) => effects.read_accumulators.push(*a_idx),
+        }
+        effects
+    }
+
+    /// Returns the label this instruction can jump to, if it is a goto instruction.
+    /// 
+    /// Used to build the control flow graph, see [referenced_label](#method.referenced_label) for all labels an instruction uses.
+    pub fn goto_label(&self) -> Option<&'a str> {
+        match self {
+            Self::Goto(label) => Some(label),
+            Self::GotoIfAccumulator(_, label, _, _) => Some(label),
+            Self::GotoIfConstant(_, label, _, _) => Some(label),
+            Self::GotoIfMemoryCell(_, label, _, _) => Some(label),
+            Self::GotoIfMemoryCellConstant(_, label, _, _) => Some(label),
+            Self::GotoIfDivisible(_, _, label) => Some(label),
+            Self::GotoIfStackPop(_, label, _) => Some(label),
+            Self::GotoIfStackTopMemoryCell(_, label, _) => Some(label),
+            Self::Call(label) => Some(label),
+            _ => None,
+        }
+    }
+
+    /// Returns the label of the program this instruction uses, that is the label it can jump to
+    /// or the label whose address it loads.
+    pub fn referenced_label(&self) -> Option<&'a str> {
+        match self {
+            Self::LoadLabelAddress(_, label) => Some(label),
+            _ => self.goto_label(),
+        }
+    }
+
+    /// Returns the labels of the memory cells this instruction addresses by name.
+    /// 
+    /// Memory cells that are addressed by a base label and a numeric suffix, like the ones of [SpillStack](#variant.SpillStack)
+    /// or [SumCells](#variant.SumCells), are not included, their labels only exist at runtime.
+    pub fn memory_cell_labels(&self) -> Vec<&'a str> {
+        match self {
+            Self::PushMemoryCell(label) => vec![label],
+            Self::PopMemoryCell(label) => vec![label],
+            Self::AssignAccumulatorValueFromMemoryCell(_, label) => vec![label],
+            Self::SwapAccumulatorMemoryCell(_, label) => vec![label],
+            Self::AssignMemoryCellValue(label, _) => vec![label],
+            Self::AssignMemoryCellValueFromAccumulator(label, _) => vec![label],
+            Self::AssingMemoryCellValueFromMemoryCell(label_a, label_b) => vec![label_a, label_b],
+            Self::StoreStackTop(label) => vec![label],
+            Self::ClearMemoryCellIf(_, label_a, _, label_b) => vec![label_a, label_b],
+            Self::CalcAccumulatorWithMemoryCell(_, _, label) => vec![label],
+            Self::CalcAccumulatorWithMemoryCells(_, _, label_a, label_b) => vec![label_a, label_b],
+            Self::CalcMemoryCellWithMemoryCellConstant(_, label_a, label_b, _) => vec![label_a, label_b],
+            Self::CalcMemoryCellWithMemoryCellAccumulator(_, label_a, label_b, _) => vec![label_a, label_b],
+            Self::CalcMemoryCellWithMemoryCells(_, label_a, label_b, label_c) => vec![label_a, label_b, label_c],
+            Self::MulAdd(_, label_a, label_b) => vec![label_a, label_b],
+            Self::GotoIfMemoryCell(_, _, _, label) => vec![label],
+            Self::GotoIfMemoryCellConstant(_, _, label, _) => vec![label],
+            Self::GotoIfStackTopMemoryCell(_, _, label) => vec![label],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns a copy of the instruction in which each label of the program that is used by the instruction is replaced by **f(label)**.
+    /// 
+    /// Memory cell labels are not changed.
+    pub fn map_labels(&self, f: impl Fn(&'a str) -> &'a str) -> Instruction<'a> {
+        match self {
+            Self::Goto(label) => Self::Goto(f(label)),
+            Self::GotoIfAccumulator(comparison, label, a_idx_a, a_idx_b) => Self::GotoIfAccumulator(comparison.clone(), f(label), *a_idx_a, *a_idx_b),
+            Self::GotoIfConstant(comparison, label, a_idx, c) => Self::GotoIfConstant(comparison.clone(), f(label), *a_idx, *c),
+            Self::GotoIfMemoryCell(comparison, label, a_idx, mcl) => Self::GotoIfMemoryCell(comparison.clone(), f(label), *a_idx, mcl),
+            Self::GotoIfMemoryCellConstant(comparison, label, mcl, c) => Self::GotoIfMemoryCellConstant(comparison.clone(), f(label), mcl, *c),
+            Self::GotoIfDivisible(a_idx, divisor, label) => Self::GotoIfDivisible(*a_idx, *divisor, f(label)),
+            Self::GotoIfStackPop(comparison, label, c) => Self::GotoIfStackPop(comparison.clone(), f(label), *c),
+            Self::GotoIfStackTopMemoryCell(comparison, label, mcl) => Self::GotoIfStackTopMemoryCell(comparison.clone(), f(label), mcl),
+            Self::LoadLabelAddress(a_idx, label) => Self::LoadLabelAddress(*a_idx, f(label)),
+            Self::Call(label) => Self::Call(f(label)),
+            _ => self.clone(),
+        }
+    }
+
+    /// Returns the instruction together with the values it would change when it is run with **runtime_args**,
+    /// for example **a0 := a0 + 3  ; a0 = 5 -> 8**.
+    /// 
+    /// Instructions that don't change a value show the values they read instead, instructions that would fail show the error.
+    /// Jump instructions are not run, they always show the values they read, see [effects](#method.effects).
+    /// **runtime_args** is not modified.
+    pub fn describe(&self, runtime_args: &RuntimeArgs<'a>) -> String {
+        let jumps = self.goto_label().is_some() || matches!(self, Self::GotoAccumulator(_) | Self::Return());
+        let mut values = Vec::new();
+        if !jumps {
+            let mut state = runtime_args.clone();
+            state.quiet = true;
+            if let Err(e) = self.run(&mut state, &mut ControlFlow::new()) {
+                return format!("{}  ; {}", self, e);
+            }
+            values.extend(runtime_args.diff_report(&state).lines().map(|line| line.replacen(": ", " = ", 1)));
+        }
+        if values.is_empty() {
+            let effects = self.effects();
+            values.extend(effects.read_accumulators.iter()
+                .map(|a_idx| format!("a{} = {}", a_idx, format_value(runtime_args.accumulators.get(*a_idx).and_then(|a| a.data)))));
+            values.extend(effects.read_memory_cells.iter()
+                .map(|label| format!("p({}) = {}", label, format_value(runtime_args.memory_cells.get(label.as_str()).and_then(|m| m.data)))));
+        }
+        if values.is_empty() {
+            self.to_string()
+        } else {
+            format!("{}  ; {}", self, values.join(", "))
+        }
+    }
+
+    /// Returns the name of the instruction variant, used as [InstructionKind](type.InstructionKind.html).
+    pub fn name(&self) -> InstructionKind {
+        match self {
+            Self::Push() => "Push",
+            Self::PushConstant(_) => "PushConstant",
+            Self::PushMemoryCell(_) => "PushMemoryCell",
+            Self::PushAllAccumulators() => "PushAllAccumulators",
+            Self::PushIf(_, _, _) => "PushIf",
+            Self::PopAllAccumulators() => "PopAllAccumulators",
+            Self::Pop() => "Pop",
+            Self::PopN(_) => "PopN",
+            Self::PopMemoryCell(_) => "PopMemoryCell",
+            Self::StackNegate() => "StackNegate",
+            Self::AssertStackEmpty() => "AssertStackEmpty",
+            Self::AssertAccumulatorsEqual(_, _) => "AssertAccumulatorsEqual",
+            Self::StackLen(_) => "StackLen",
+            Self::StackLenMinus(_, _) => "StackLenMinus",
+            Self::SpillStack(_) => "SpillStack",
+            Self::StackRotate(_) => "StackRotate",
+            Self::AssignAccumulatorValue(_, _) => "AssignAccumulatorValue",
+            Self::AssignIf(_, _, _, _, _, _) => "AssignIf",
+            Self::StoreAccumulators(_, _) => "StoreAccumulators",
+            Self::AssignAccumulatorValueFromAccumulator(_, _) => "AssignAccumulatorValueFromAccumulator",
+            Self::MoveAccumulator(_, _) => "MoveAccumulator",
+            Self::AssignAccumulatorFromIndexed(_, _) => "AssignAccumulatorFromIndexed",
+            Self::AssignAccumulatorValueFromMemoryCell(_, _) => "AssignAccumulatorValueFromMemoryCell",
+            Self::SwapAccumulatorMemoryCell(_, _) => "SwapAccumulatorMemoryCell",
+            Self::AssignMemoryCellValue(_, _) => "AssignMemoryCellValue",
+            Self::AssignMemoryCellValueFromAccumulator(_, _) => "AssignMemoryCellValueFromAccumulator",
+            Self::AssingMemoryCellValueFromMemoryCell(_, _) => "AssingMemoryCellValueFromMemoryCell",
+            Self::StoreStackTop(_) => "StoreStackTop",
+            Self::ClearMemoryCellIf(_, _, _, _) => "ClearMemoryCellIf",
+            Self::CalcAccumulatorWithConstant(_, _, _) => "CalcAccumulatorWithConstant",
+            Self::CalcAccumulatorWithAccumulator(_, _, _) => "CalcAccumulatorWithAccumulator",
+            Self::CalcAccumulatorWithAccumulators(_, _, _, _) => "CalcAccumulatorWithAccumulators",
+            Self::CalcAccumulatorWithMemoryCell(_, _, _) => "CalcAccumulatorWithMemoryCell",
+            Self::CalcAccumulatorWithMemoryCells(_, _, _, _) => "CalcAccumulatorWithMemoryCells",
+            Self::CalcAccumulatorWithStackTop(_, _) => "CalcAccumulatorWithStackTop",
+            Self::CalcMemoryCellWithMemoryCellConstant(_, _, _, _) => "CalcMemoryCellWithMemoryCellConstant",
+            Self::CalcMemoryCellWithMemoryCellAccumulator(_, _, _, _) => "CalcMemoryCellWithMemoryCellAccumulator",
+            Self::CalcMemoryCellWithMemoryCells(_, _, _, _) => "CalcMemoryCellWithMemoryCells",
+            Self::MulAdd(_, _, _) => "MulAdd",
+            Self::AbsDiff(_, _, _) => "AbsDiff",
+            Self::TransformAccumulator(_, _) => "TransformAccumulator",
+            Self::Gcd(_, _, _) => "Gcd",
+            Self::PopCount(_, _) => "PopCount",
+            Self::MinOfCells(_, _, _) => "MinOfCells",
+            Self::MaxOfCells(_, _, _) => "MaxOfCells",
+            Self::SumCells(_, _, _) => "SumCells",
+            Self::RotateLeft(_, _) => "RotateLeft",
+            Self::RotateRight(_, _) => "RotateRight",
+            Self::Goto(_) => "Goto",
+            Self::GotoIfAccumulator(_, _, _, _) => "GotoIfAccumulator",
+            Self::GotoIfConstant(_, _, _, _) => "GotoIfConstant",
+            Self::GotoIfMemoryCell(_, _, _, _) => "GotoIfMemoryCell",
+            Self::GotoIfMemoryCellConstant(_, _, _, _) => "GotoIfMemoryCellConstant",
+            Self::GotoIfDivisible(_, _, _) => "GotoIfDivisible",
+            Self::GotoIfStackPop(_, _, _) => "GotoIfStackPop",
+            Self::GotoIfStackTopMemoryCell(_, _, _) => "GotoIfStackTopMemoryCell",
+            Self::LoadLabelAddress(_, _) => "LoadLabelAddress",
+            Self::GotoAccumulator(_) => "GotoAccumulator",
+            Self::Call(_) => "Call",
+            Self::Return() => "Return",
+            Self::ResetState() => "ResetState",
+            Self::Nop() => "Nop",
+            Self::PrintAccumulators() => "PrintAccumulators",
+            Self::PrintMemoryCells() => "PrintMemoryCells",
+            Self::PrintStack() => "PrintStack",
+            Self::PrintLabeled(_, _) => "PrintLabeled",
+        }
+    }
+
+    /// Returns the operation this instruction performs, if it is a calc instruction.
+    pub fn operation(&self) -> Option<&Operation> {
+        match self {
+            Self::CalcAccumulatorWithConstant(operation, _, _) => Some(operation),
+            Self::CalcAccumulatorWithAccumulator(operation, _, _) => Some(operation),
+            Self::CalcAccumulatorWithAccumulators(operation, _, _, _) => Some(operation),
+            Self::CalcAccumulatorWithMemoryCell(operation, _, _) => Some(operation),
+            Self::CalcAccumulatorWithMemoryCells(operation, _, _, _) => Some(operation),
+            Self::CalcAccumulatorWithStackTop(operation, _) => Some(operation),
+            Self::CalcMemoryCellWithMemoryCellConstant(operation, _, _, _) => Some(operation),
+            Self::CalcMemoryCellWithMemoryCellAccumulator(operation, _, _, _) => Some(operation),
+            Self::CalcMemoryCellWithMemoryCells(operation, _, _, _) => Some(operation),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Instruction<'_> {
+    /// Formats the instruction in the notation used in the lecture, accumulators are written as **a0** and memory cells as **p(label)**.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Push() => write!(f, "push"),
+            Self::PushConstant(value) => write!(f, "push {}", value),
+            Self::PushMemoryCell(label) => write!(f, "push p({})", label),
+            Self::PushAllAccumulators() => write!(f, "push all accumulators"),
+            Self::PushIf(comparison, a_idx, value) => write!(f, "if a{} {} {} then push a{}", a_idx, comparison, value, a_idx),
+            Self::PopAllAccumulators() => write!(f, "pop all accumulators"),
+            Self::Pop() => write!(f, "pop"),
+            Self::PopN(n) => write!(f, "pop {} values", n),
+            Self::PopMemoryCell(label) => write!(f, "p({}) := pop", label),
+            Self::StackNegate() => write!(f, "s := -s"),
+            Self::AssertStackEmpty() => write!(f, "assert stack is empty"),
+            Self::AssertAccumulatorsEqual(a_idx_a, a_idx_b) => write!(f, "assert a{} = a{}", a_idx_a, a_idx_b),
+            Self::StackLen(a_idx) => write!(f, "a{} := stack length", a_idx),
+            Self::StackLenMinus(a_idx, value) => write!(f, "a{} := stack length - {}", a_idx, value),
+            Self::SpillStack(base_label) => write!(f, "p({}0), ... := stack", base_label),
+            Self::StackRotate(n) => write!(f, "rotate top {} stack values", n),
+            Self::AssignAccumulatorValue(a_idx, value) => write!(f, "a{} := {}", a_idx, value),
+            Self::AssignIf(a_idx_a, comparison, a_idx_b, value, if_true, if_false) => write!(f, "a{} := if a{} {} {} then {} else {}", a_idx_a, a_idx_b, comparison, value, if_true, if_false),
+            Self::StoreAccumulators(count, base_label) => write!(f, "p({}0), ..., p({}{}) := a0, ..., a{}", base_label, base_label, count.saturating_sub(1), count.saturating_sub(1)),
+            Self::AssignAccumulatorValueFromAccumulator(a_idx_a, a_idx_b) => write!(f, "a{} := a{}", a_idx_a, a_idx_b),
+            Self::MoveAccumulator(a_idx_a, a_idx_b) => write!(f, "a{} := a{}, a{} := None", a_idx_a, a_idx_b, a_idx_b),
+            Self::AssignAccumulatorFromIndexed(a_idx_a, a_idx_b) => write!(f, "a{} := a(a{})", a_idx_a, a_idx_b),
+            Self::AssignAccumulatorValueFromMemoryCell(a_idx, label) => write!(f, "a{} := p({})", a_idx, label),
+            Self::SwapAccumulatorMemoryCell(a_idx, label) => write!(f, "a{} :=: p({})", a_idx, label),
+            Self::AssignMemoryCellValue(label, value) => write!(f, "p({}) := {}", label, value),
+            Self::AssignMemoryCellValueFromAccumulator(label, a_idx) => write!(f, "p({}) := a{}", label, a_idx),
+            Self::AssingMemoryCellValueFromMemoryCell(label_a, label_b) => write!(f, "p({}) := p({})", label_a, label_b),
+            Self::StoreStackTop(label) => write!(f, "p({}) := s", label),
+            Self::ClearMemoryCellIf(comparison, label_a, value, label_b) => write!(f, "if p({}) {} {} then p({}) := None", label_a, comparison, value, label_b),
+            Self::CalcAccumulatorWithConstant(operation, a_idx, value) => write!(f, "a{} := a{} {} {}", a_idx, a_idx, operation, value),
+            Self::CalcAccumulatorWithAccumulator(operation, a_idx_a, a_idx_b) => write!(f, "a{} := a{} {} a{}", a_idx_a, a_idx_a, operation, a_idx_b),
+            Self::CalcAccumulatorWithAccumulators(operation, a_idx_a, a_idx_b, a_idx_c) => write!(f, "a{} := a{} {} a{}", a_idx_a, a_idx_b, operation, a_idx_c),
+            Self::CalcAccumulatorWithMemoryCell(operation, a_idx, label) => write!(f, "a{} := a{} {} p({})", a_idx, a_idx, operation, label),
+            Self::CalcAccumulatorWithMemoryCells(operation, a_idx, label_a, label_b) => write!(f, "a{} := p({}) {} p({})", a_idx, label_a, operation, label_b),
+            Self::CalcAccumulatorWithStackTop(operation, a_idx) => write!(f, "a{} := a{} {} s", a_idx, a_idx, operation),
+            Self::CalcMemoryCellWithMemoryCellConstant(operation, label_a, label_b, value) => write!(f, "p({}) := p({}) {} {}", label_a, label_b, operation, value),
+            Self::CalcMemoryCellWithMemoryCellAccumulator(operation, label_a, label_b, a_idx) => write!(f, "p({}) := p({}) {} a{}", label_a, label_b, operation, a_idx),
+            Self::CalcMemoryCellWithMemoryCells(operation, label_a, label_b, label_c) => write!(f, "p({}) := p({}) {} p({})", label_a, label_b, operation, label_c),
+            Self::MulAdd(a_idx, label_a, label_b) => write!(f, "a{} := a{} + p({}) * p({})", a_idx, a_idx, label_a, label_b),
+            Self::AbsDiff(a_idx_a, a_idx_b, a_idx_c) => write!(f, "a{} := |a{} - a{}|", a_idx_a, a_idx_b, a_idx_c),
+            Self::TransformAccumulator(a_idx, transform) => write!(f, "a{} := {}(a{})", a_idx, transform, a_idx),
+            Self::Gcd(a_idx_a, a_idx_b, a_idx_c) => write!(f, "a{} := gcd(a{}, a{})", a_idx_a, a_idx_b, a_idx_c),
+            Self::PopCount(a_idx_a, a_idx_b) => write!(f, "a{} := popcount(a{})", a_idx_a, a_idx_b),
+            Self::MinOfCells(a_idx, base_label, count) => write!(f, "a{} := min(p({}0), ..., p({}{}))", a_idx, base_label, base_label, count.saturating_sub(1)),
+            Self::MaxOfCells(a_idx, base_label, count) => write!(f, "a{} := max(p({}0), ..., p({}{}))", a_idx, base_label, base_label, count.saturating_sub(1)),
+            Self::SumCells(a_idx, base_label, count) => write!(f, "a{} := p({}0) + ... + p({}{})", a_idx, base_label, base_label, count.saturating_sub(1)),
+            Self::RotateLeft(a_idx, n) => write!(f, "a{} := a{} rotl {}", a_idx, a_idx, n),
+            Self::RotateRight(a_idx, n) => write!(f, "a{} := a{} rotr {}", a_idx, a_idx, n),
+            Self::Goto(label) => write!(f, "goto {}", label),
+            Self::GotoIfAccumulator(comparison, label, a_idx_a, a_idx_b) => write!(f, "if a{} {} a{} then goto {}", a_idx_a, comparison, a_idx_b, label),
+            Self::GotoIfConstant(comparison, label, a_idx, c) => write!(f, "if a{} {} {} then goto {}", a_idx, comparison, c, label),
+            Self::GotoIfMemoryCell(comparison, label, a_idx, mcl) => write!(f, "if a{} {} p({}) then goto {}", a_idx, comparison, mcl, label),
+            Self::GotoIfMemoryCellConstant(comparison, label, mcl, c) => write!(f, "if p({}) {} {} then goto {}", mcl, comparison, c, label),
+            Self::GotoIfDivisible(a_idx, divisor, label) => write!(f, "if a{} % {} = 0 then goto {}", a_idx, divisor, label),
+            Self::GotoIfStackPop(comparison, label, c) => write!(f, "if pop {} {} then goto {}", comparison, c, label),
+            Self::GotoIfStackTopMemoryCell(comparison, label, mcl) => write!(f, "if s {} p({}) then goto {}", comparison, mcl, label),
+            Self::LoadLabelAddress(a_idx, label) => write!(f, "a{} := address of {}", a_idx, label),
+            Self::GotoAccumulator(a_idx) => write!(f, "goto a{}", a_idx),
+            Self::Call(label) => write!(f, "call {}", label),
+            Self::Return() => write!(f, "return"),
+            Self::ResetState() => write!(f, "reset state"),
+            Self::Nop() => write!(f, "nop"),
+            Self::PrintAccumulators() => write!(f, "print accumulators"),
+            Self::PrintMemoryCells() => write!(f, "print memory cells"),
+            Self::PrintStack() => write!(f, "print stack"),
+            Self::PrintLabeled(message, a_idx) => write!(f, "print \"{}: \" a{}", message, a_idx),
+        }
+    }
+}
+
+/// The kind of an instruction, equal to the name of its variant, for example **PushConstant**.
+/// 
+/// See [name](enum.Instruction.html#method.name)
+pub type InstructionKind = &'static str;
+
+/// Accumulators and memory cells that are accessed by an instruction.
+/// 
+/// See [effects](enum.Instruction.html#method.effects)
+#[derive(Default, Debug, PartialEq)]
+#[allow(dead_code)]
+pub struct Effects {
+    /// Indices of the accumulators that are read
+    pub read_accumulators: Vec<usize>,
+    /// Indices of the accumulators that are written
+    pub written_accumulators: Vec<usize>,
+    /// Labels of the memory cells that are read
+    pub read_memory_cells: Vec<String>,
+    /// Labels of the memory cells that are written
+    pub written_memory_cells: Vec<String>,
 }
 
 /// Runs code equal to **push**
-fn push(runtime_args: &mut RuntimeArgs) -> Result<(), String> {
+fn push(runtime_args: &mut RuntimeArgs) -> Result<(), RuntimeError> {
     assert_accumulator_exists(runtime_args, &0)?;
     runtime_args.stack.push(runtime_args.accumulators[0].data.unwrap_or(0));
     Ok(())
 }
 
+/// Runs code equal to **push x**
+/// 
+/// - x = constant with value **value**
+fn push_constant(runtime_args: &mut RuntimeArgs, value: &i32) {
+    runtime_args.stack.push(*value);
+}
+
+/// Runs code equal to **push p(i)**
+/// 
+/// - p(i) = value of memory cell with label **label**
+fn push_memory_cell(runtime_args: &mut RuntimeArgs, label: &str) -> Result<(), RuntimeError> {
+    let value = assert_memory_cell_contains_value(runtime_args, label)?;
+    runtime_args.stack.push(value);
+    Ok(())
+}
+
+/// Pushes the values of all accumulators onto the stack, starting with accumulator 0.
+/// 
+/// Accumulators without a value are pushed as 0, like in [push](fn.push.html).
+/// Accumulators above the [accumulator_soft_limit](../runtime/struct.RuntimeArgs.html#structfield.accumulator_soft_limit) are not pushed.
+fn push_all_accumulators(runtime_args: &mut RuntimeArgs) {
+    let count = runtime_args.accessible_accumulators();
+    for accumulator in &runtime_args.accumulators[..count] {
+        runtime_args.stack.push(accumulator.data.unwrap_or(0));
+    }
+}
+
+/// Runs code equal to **if a cmp x then push a**
+/// 
+/// - a = value of accumulator with index **a_idx**
+/// - x = constant with value **value**
+/// - cmp = the way how **a** and **x** should be compared
+fn push_if(runtime_args: &mut RuntimeArgs, comparison: &Comparison, a_idx: &usize, value: &i32) -> Result<(), RuntimeError> {
+    let a = assert_accumulator_contains_value(runtime_args, a_idx)?;
+    if comparison.cmp(a, *value) {
+        runtime_args.stack.push(a);
+    }
+    Ok(())
+}
+
+/// Pops values from the stack into all accumulators, starting with the last accumulator,
+/// so that it restores the values saved with [push_all_accumulators](fn.push_all_accumulators.html).
+/// 
+/// Accumulators above the [accumulator_soft_limit](../runtime/struct.RuntimeArgs.html#structfield.accumulator_soft_limit) are not changed.
+/// 
+/// Errors when the stack contains less values than there are accumulators, the stack is not modified in that case.
+fn pop_all_accumulators(runtime_args: &mut RuntimeArgs) -> Result<(), RuntimeError> {
+    let count = runtime_args.accessible_accumulators();
+    if runtime_args.stack.len() < count {
+        return Err(RuntimeError::StackTooSmall(count, runtime_args.stack.len()));
+    }
+    for accumulator in runtime_args.accumulators[..count].iter_mut().rev() {
+        accumulator.data = runtime_args.stack.pop();
+    }
+    Ok(())
+}
+
 /// Runs code equal to **pop**
-fn pop(runtime_args: &mut RuntimeArgs) -> Result<(), String> {
+fn pop(runtime_args: &mut RuntimeArgs) -> Result<(), RuntimeError> {
     assert_accumulator_contains_value(runtime_args, &0)?;
     runtime_args.accumulators[0].data = Some(runtime_args.stack.pop().unwrap_or(0));
     Ok(())
 }
 
+/// Removes the top **n** values from the stack without storing them.
+/// 
+/// Errors when the stack contains less than **n** values, the stack is not modified in that case.
+fn pop_n(runtime_args: &mut RuntimeArgs, n: &usize) -> Result<(), RuntimeError> {
+    let len = runtime_args.stack.len();
+    if len < *n {
+        return Err(RuntimeError::StackTooSmall(*n, len));
+    }
+    runtime_args.stack.truncate(len - n);
+    Ok(())
+}
+
+/// Runs code equal to **p(i) := pop**
+/// 
+/// - p(i) = value of memory cell with label **label**
+/// 
+/// Errors when the stack is empty, the stack is not modified when the memory cell does not exist.
+fn pop_memory_cell(runtime_args: &mut RuntimeArgs, label: &str) -> Result<(), RuntimeError> {
+    assert_memory_cell_exists(runtime_args, label)?;
+    let value = runtime_args.stack.pop().ok_or(RuntimeError::StackEmpty)?;
+    runtime_args.memory_cells.get_mut(label).unwrap().data = Some(value);
+    Ok(())
+}
+
+/// Runs code equal to **s := -s**
+/// 
+/// - s = value on top of the stack
+/// 
+/// Errors when the stack is empty or when the value can not be negated (i32::MIN).
+fn stack_negate(runtime_args: &mut RuntimeArgs) -> Result<(), RuntimeError> {
+    let value = runtime_args.stack.last_mut().ok_or(RuntimeError::StackEmpty)?;
+    *value = value.checked_neg().ok_or_else(|| RuntimeError::Overflow(format!("-({})", value)))?;
+    Ok(())
+}
+
+/// Errors when the stack still contains values.
+fn assert_stack_empty(runtime_args: &mut RuntimeArgs) -> Result<(), RuntimeError> {
+    if runtime_args.stack.is_empty() {
+        Ok(())
+    } else {
+        Err(RuntimeError::StackNotEmpty(runtime_args.stack.len()))
+    }
+}
+
+/// Runs code equal to **assert a = b**
+/// 
+/// - a = value of accumulator with index **a_idx_a**
+/// - b = value of accumulator with index **a_idx_b**
+/// 
+/// Errors when the values differ, the error contains both values.
+fn assert_accumulators_equal(runtime_args: &mut RuntimeArgs, a_idx_a: &usize, a_idx_b: &usize) -> Result<(), RuntimeError> {
+    let a = assert_accumulator_contains_value(runtime_args, a_idx_a)?;
+    let b = assert_accumulator_contains_value(runtime_args, a_idx_b)?;
+    if a == b {
+        Ok(())
+    } else {
+        Err(RuntimeError::AssertionFailed(format!("a{} = a{} (a{} = {}, a{} = {})", a_idx_a, a_idx_b, a_idx_a, a, a_idx_b, b)))
+    }
+}
+
+/// Runs code equal to **a := stack length**
+/// 
+/// - a = value of accumulator with index **a_idx**
+fn stack_len(runtime_args: &mut RuntimeArgs, a_idx: &usize) -> Result<(), RuntimeError> {
+    assert_accumulator_exists(runtime_args, a_idx)?;
+    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(runtime_args.stack.len() as i32);
+    Ok(())
+}
+
+/// Runs code equal to **a := stack length - x**
+/// 
+/// - a = value of accumulator with index **a_idx**
+/// - x = constant with value **value**
+/// 
+/// The result is not clamped, it is negative when **x** is larger than the stack length.
+/// Errors when the result does not fit into an i32.
+fn stack_len_minus(runtime_args: &mut RuntimeArgs, a_idx: &usize, value: &i32) -> Result<(), RuntimeError> {
+    assert_accumulator_exists(runtime_args, a_idx)?;
+    let len = runtime_args.stack.len() as i32;
+    let result = len.checked_sub(*value).ok_or_else(|| RuntimeError::Overflow(format!("{} - {}", len, value)))?;
+    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(result);
+    Ok(())
+}
+
+/// Runs code equal to **p(base0) := s0, ..., p(base(n-1)) := s(n-1)**
+/// 
+/// - s0, ..., s(n-1) = values on the stack, starting with the bottom of the stack
+/// - base = label of the memory cells without the numeric suffix, given by **base_label**
+/// 
+/// The stack is not modified. Nothing is written when one of the memory cells is missing.
+fn spill_stack(runtime_args: &mut RuntimeArgs, base_label: &str) -> Result<(), RuntimeError> {
+    let labels: Vec<String> = (0..runtime_args.stack.len()).map(|i| format!("{}{}", base_label, i)).collect();
+    for label in &labels {
+        assert_memory_cell_exists(runtime_args, label)?;
+    }
+    for (label, value) in labels.iter().zip(runtime_args.stack.iter()) {
+        runtime_args.memory_cells.get_mut(label.as_str()).unwrap().data = Some(*value);
+    }
+    Ok(())
+}
+
+/// Rotates the top **n** values of the stack: the top value is moved down to position n, the other values move up by one.
+/// 
+/// For example rotating the top 3 values of [1, 2, 3] results in [3, 1, 2].
+/// 
+/// Errors when the stack contains less than **n** values.
+fn stack_rotate(runtime_args: &mut RuntimeArgs, n: &usize) -> Result<(), RuntimeError> {
+    let len = runtime_args.stack.len();
+    if len < *n {
+        return Err(RuntimeError::StackTooSmall(*n, len));
+    }
+    runtime_args.stack[len - n..].rotate_right(1.min(*n));
+    Ok(())
+}
+
 /// Runs code equal to **a := x**
 /// 
 /// - a = value of accumulator with index **a_idx**
 /// - x = constant with value **value**
-fn assign_accumulator_value(runtime_args: &mut RuntimeArgs, a_idx: &usize, value: &i32) -> Result<(), String> {
+fn assign_accumulator_value(runtime_args: &mut RuntimeArgs, a_idx: &usize, value: &i32) -> Result<(), RuntimeError> {
     assert_accumulator_exists(runtime_args, a_idx)?;
     runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(*value);
     Ok(())
 }
 
+/// Runs code equal to **a := if b cmp x then y else z**
+/// 
+/// - a = value of accumulator with index **a_idx_a**
+/// - b = value of accumulator with index **a_idx_b**
+/// - x = constant with value **value**
+/// - y = constant with value **if_true**
+/// - z = constant with value **if_false**
+/// - cmp = the way how **b** and **x** should be compared
+fn assign_if(runtime_args: &mut RuntimeArgs, a_idx_a: &usize, comparison: &Comparison, a_idx_b: &usize, value: &i32, if_true: &i32, if_false: &i32) -> Result<(), RuntimeError> {
+    assert_accumulator_exists(runtime_args, a_idx_a)?;
+    let b = assert_accumulator_contains_value(runtime_args, a_idx_b)?;
+    let result = if comparison.cmp(b, *value) { *if_true } else { *if_false };
+    runtime_args.accumulators.get_mut(*a_idx_a).unwrap().data = Some(result);
+    Ok(())
+}
+
+/// Runs code equal to **p(base0) := a0, ..., p(base(n-1)) := a(n-1)**
+/// 
+/// - n = number of accumulators to store, given by **count**
+/// - base = label of the memory cells without the numeric suffix, given by **base_label**
+/// 
+/// Nothing is written when one of the accumulators or memory cells is missing.
+fn store_accumulators(runtime_args: &mut RuntimeArgs, count: &usize, base_label: &str) -> Result<(), RuntimeError> {
+    let mut values = Vec::new();
+    for i in 0..*count {
+        let label = format!("{}{}", base_label, i);
+        assert_memory_cell_exists(runtime_args, &label)?;
+        values.push((label, assert_accumulator_contains_value(runtime_args, &i)?));
+    }
+    for (label, value) in values {
+        runtime_args.memory_cells.get_mut(label.as_str()).unwrap().data = Some(value);
+    }
+    Ok(())
+}
+
 /// Runs code equal to **a := b**
 /// 
 /// - a = value of accumulator with index **a_idx_a**
 /// - b = value of accumulator with index **a_idx_b**
-fn assign_accumulator_value_from_accumulator(runtime_args: &mut RuntimeArgs, a_idx_a: &usize, a_idx_b: &usize) -> Result<(), String> {
+fn assign_accumulator_value_from_accumulator(runtime_args: &mut RuntimeArgs, a_idx_a: &usize, a_idx_b: &usize) -> Result<(), RuntimeError> {
+    let src = assert_accumulator_contains_value(runtime_args, a_idx_b)?;
+    assert_accumulator_exists(runtime_args, a_idx_a)?;
+    runtime_args.accumulators.get_mut(*a_idx_a).unwrap().data = Some(src);
+    Ok(())
+}
+
+/// Runs code equal to **a := b, b := None**
+/// 
+/// - a = value of accumulator with index **a_idx_a**
+/// - b = value of accumulator with index **a_idx_b**
+/// 
+/// When **a** and **b** are the same accumulator it keeps its value.
+fn move_accumulator(runtime_args: &mut RuntimeArgs, a_idx_a: &usize, a_idx_b: &usize) -> Result<(), RuntimeError> {
     let src = assert_accumulator_contains_value(runtime_args, a_idx_b)?;
     assert_accumulator_exists(runtime_args, a_idx_a)?;
+    runtime_args.accumulators.get_mut(*a_idx_b).unwrap().data = None;
+    runtime_args.accumulators.get_mut(*a_idx_a).unwrap().data = Some(src);
+    Ok(())
+}
+
+/// Runs code equal to **a := a(b)**
+/// 
+/// - a = value of accumulator with index **a_idx_a**
+/// - b = value of accumulator with index **a_idx_b**, used as index of the accumulator to read
+/// 
+/// Errors when **b** is negative or no accumulator with index **b** exists.
+fn assign_accumulator_from_indexed(runtime_args: &mut RuntimeArgs, a_idx_a: &usize, a_idx_b: &usize) -> Result<(), RuntimeError> {
+    let index = assert_accumulator_contains_value(runtime_args, a_idx_b)?;
+    let index = usize::try_from(index).map_err(|_| RuntimeError::InvalidIndex(index))?;
+    let src = assert_accumulator_contains_value(runtime_args, &index)?;
+    assert_accumulator_exists(runtime_args, a_idx_a)?;
     runtime_args.accumulators.get_mut(*a_idx_a).unwrap().data = Some(src);
     Ok(())
 }
@@ -161,18 +1073,28 @@ fn assign_accumulator_value_from_accumulator(runtime_args: &mut RuntimeArgs, a_i
 /// 
 /// - a = value of accumulator with index **a_idx**
 /// - p(i) = value of memory cell with label **label**
-fn assign_accumulator_value_from_memory_cell(runtime_args: &mut RuntimeArgs, a_idx: &usize, label: &str) -> Result<(), String> {
+fn assign_accumulator_value_from_memory_cell(runtime_args: &mut RuntimeArgs, a_idx: &usize, label: &str) -> Result<(), RuntimeError> {
     assert_accumulator_exists(runtime_args, a_idx)?;
     let value = assert_memory_cell_contains_value(runtime_args, label)?;
     runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(value);
     Ok(())
 }
 
+/// Exchanges the value of the accumulator with index **a_idx** with the value of the memory cell with label **label**.
+/// 
+/// Empty values are exchanged as well.
+fn swap_accumulator_memory_cell(runtime_args: &mut RuntimeArgs, a_idx: &usize, label: &str) -> Result<(), RuntimeError> {
+    assert_accumulator_exists(runtime_args, a_idx)?;
+    assert_memory_cell_exists(runtime_args, label)?;
+    std::mem::swap(&mut runtime_args.accumulators[*a_idx].data, &mut runtime_args.memory_cells.get_mut(label).unwrap().data);
+    Ok(())
+}
+
 /// Runs code equal to **p(i) := x**
 /// 
 /// - p(i) = value of memory cell with label **label**
 /// - x = constant with value **value**
-fn assign_memory_cell_value(runtime_args: &mut RuntimeArgs, label: &str, value: &i32) -> Result<(), String> {
+fn assign_memory_cell_value(runtime_args: &mut RuntimeArgs, label: &str, value: &i32) -> Result<(), RuntimeError> {
     assert_memory_cell_exists(runtime_args, label)?;
     runtime_args.memory_cells.get_mut(label).unwrap().data = Some(*value);
     Ok(())
@@ -182,7 +1104,7 @@ fn assign_memory_cell_value(runtime_args: &mut RuntimeArgs, label: &str, value:
 /// 
 /// - p(i) = value of memory cell with label **label**
 /// - a = value of accumulator with index **a_idx**
-fn assign_memory_cell_value_from_accumulator(runtime_args: &mut RuntimeArgs, label: &str, a_idx: &usize) -> Result<(), String> {
+fn assign_memory_cell_value_from_accumulator(runtime_args: &mut RuntimeArgs, label: &str, a_idx: &usize) -> Result<(), RuntimeError> {
     assert_memory_cell_exists(runtime_args, label)?;
     let value = assert_accumulator_contains_value(runtime_args, a_idx)?;
     runtime_args.memory_cells.get_mut(label).unwrap().data = Some(value);
@@ -193,21 +1115,47 @@ fn assign_memory_cell_value_from_accumulator(runtime_args: &mut RuntimeArgs, lab
 /// 
 /// - p(i) = value of memory cell with label **label_a**
 /// - p(j) = value of memory cell with label **label_b**
-fn assign_memory_cell_value_from_memory_cell(runtime_args: &mut RuntimeArgs, label_a: &str, label_b: &str) -> Result<(), String> {
+fn assign_memory_cell_value_from_memory_cell(runtime_args: &mut RuntimeArgs, label_a: &str, label_b: &str) -> Result<(), RuntimeError> {
     assert_memory_cell_exists(runtime_args, label_a)?;
     let value = assert_memory_cell_contains_value(runtime_args, label_b)?;
     runtime_args.memory_cells.get_mut(label_a).unwrap().data = Some(value);
     Ok(())
 }
 
+/// Runs code equal to **p(i) := s**
+/// 
+/// - p(i) = value of memory cell with label **label**
+/// - s = value on top of the stack, the stack is not modified
+fn store_stack_top(runtime_args: &mut RuntimeArgs, label: &str) -> Result<(), RuntimeError> {
+    assert_memory_cell_exists(runtime_args, label)?;
+    let value = *runtime_args.stack.last().ok_or(RuntimeError::StackEmpty)?;
+    runtime_args.memory_cells.get_mut(label).unwrap().data = Some(value);
+    Ok(())
+}
+
+/// Runs code equal to **if p(i) cmp x then p(j) := None**
+/// 
+/// - p(i) = value of memory cell with label **label_a**
+/// - x = constant with value **value**
+/// - p(j) = memory cell with label **label_b** that is cleared
+/// - cmp = the way how **p(i)** and **x** should be compared
+fn clear_memory_cell_if(runtime_args: &mut RuntimeArgs, comparison: &Comparison, label_a: &str, value: &i32, label_b: &str) -> Result<(), RuntimeError> {
+    assert_memory_cell_exists(runtime_args, label_b)?;
+    let a = assert_memory_cell_contains_value(runtime_args, label_a)?;
+    if comparison.cmp(a, *value) {
+        runtime_args.memory_cells.get_mut(label_b).unwrap().data = None;
+    }
+    Ok(())
+}
+
 /// Runs code equal to **a := a op x**
 /// 
 /// - a = value of accumulator with index **a_idx**
 /// - x = constant with value **value**
 /// - op = the operation to perform
-fn calc_accumulator_with_constant(runtime_args: &mut RuntimeArgs, operation: &Operation, a_idx: &usize, value: &i32) -> Result<(), String> {
+fn calc_accumulator_with_constant(runtime_args: &mut RuntimeArgs, operation: &Operation, a_idx: &usize, value: &i32) -> Result<(), RuntimeError> {
     let v = assert_accumulator_contains_value(runtime_args, a_idx)?;
-    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(operation.calc(v, *value));
+    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(operation.calc(v, *value)?);
     Ok(())
 }
 
@@ -216,10 +1164,10 @@ fn calc_accumulator_with_constant(runtime_args: &mut RuntimeArgs, operation: &Op
 /// - a = accumulator with index **a_idx_a**
 /// - b = accumulator with index **a_idx_b**
 /// - op = the operation to perform
-fn calc_accumulator_with_accumulator(runtime_args: &mut RuntimeArgs, operation: &Operation, a_idx_a: &usize, a_idx_b: &usize) -> Result<(), String> {
+fn calc_accumulator_with_accumulator(runtime_args: &mut RuntimeArgs, operation: &Operation, a_idx_a: &usize, a_idx_b: &usize) -> Result<(), RuntimeError> {
     let a = assert_accumulator_contains_value(runtime_args, a_idx_a)?;
     let b = assert_accumulator_contains_value(runtime_args, a_idx_b)?;
-    runtime_args.accumulators.get_mut(*a_idx_a).unwrap().data = Some(operation.calc(a, b));
+    runtime_args.accumulators.get_mut(*a_idx_a).unwrap().data = Some(operation.calc(a, b)?);
     Ok(())
 }
 
@@ -229,11 +1177,11 @@ fn calc_accumulator_with_accumulator(runtime_args: &mut RuntimeArgs, operation:
 /// - b = value of accumulator with index **a_idx_b**
 /// - c = value of accumulator with index **a_idx_c**
 /// - op = the operation to perform
-fn calc_accumulator_with_accumulators(runtime_args: &mut RuntimeArgs, operation: &Operation, a_idx_a: &usize, a_idx_b: &usize, a_idx_c: &usize) -> Result<(), String> {
+fn calc_accumulator_with_accumulators(runtime_args: &mut RuntimeArgs, operation: &Operation, a_idx_a: &usize, a_idx_b: &usize, a_idx_c: &usize) -> Result<(), RuntimeError> {
     assert_accumulator_exists(runtime_args, a_idx_a)?;
     let a = assert_accumulator_contains_value(runtime_args, a_idx_b)?;
     let b = assert_accumulator_contains_value(runtime_args, a_idx_c)?;
-    runtime_args.accumulators.get_mut(*a_idx_a).unwrap().data = Some(operation.calc(a, b));
+    runtime_args.accumulators.get_mut(*a_idx_a).unwrap().data = Some(operation.calc(a, b)?);
     Ok(())
 }
 
@@ -242,10 +1190,10 @@ fn calc_accumulator_with_accumulators(runtime_args: &mut RuntimeArgs, operation:
 /// - a = value of accumulator with index **a_idx**
 /// - p(i) = value of memory cell with label **label**
 /// - op = the operation to perform
-fn calc_accumulator_with_memory_cell(runtime_args: &mut RuntimeArgs, operation: &Operation, a_idx: &usize, label: &str) -> Result<(), String> {
+fn calc_accumulator_with_memory_cell(runtime_args: &mut RuntimeArgs, operation: &Operation, a_idx: &usize, label: &str) -> Result<(), RuntimeError> {
     let a = assert_accumulator_contains_value(runtime_args, a_idx)?;
     let b = assert_memory_cell_contains_value(runtime_args, label)?;
-    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(operation.calc(a, b));
+    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(operation.calc(a, b)?);
     Ok(())
 }
 
@@ -255,11 +1203,25 @@ fn calc_accumulator_with_memory_cell(runtime_args: &mut RuntimeArgs, operation:
 /// - p(i) = value of memory cell with label **label_a**
 /// - p(j) = value of memory cell with label **label_b**
 /// - op = the operation to perform
-fn calc_accumulator_with_memory_cells(runtime_args: &mut RuntimeArgs, operation: &Operation, a_idx: &usize, label_a: &str, label_b: &str) -> Result<(), String> {
+fn calc_accumulator_with_memory_cells(runtime_args: &mut RuntimeArgs, operation: &Operation, a_idx: &usize, label_a: &str, label_b: &str) -> Result<(), RuntimeError> {
     assert_accumulator_exists(runtime_args, a_idx)?;
     let a = assert_memory_cell_contains_value(runtime_args, label_a)?;
     let b = assert_memory_cell_contains_value(runtime_args, label_b)?;
-    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(operation.calc(a, b));
+    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(operation.calc(a, b)?);
+    Ok(())
+}
+
+/// Runs code equal to **a := a op s**
+/// 
+/// - a = value of accumulator with index **a_idx**
+/// - s = value on top of the stack, the stack is not modified
+/// - op = the operation to perform
+/// 
+/// Errors when the stack is empty or when the calculation fails.
+fn calc_accumulator_with_stack_top(runtime_args: &mut RuntimeArgs, operation: &Operation, a_idx: &usize) -> Result<(), RuntimeError> {
+    let a = assert_accumulator_contains_value(runtime_args, a_idx)?;
+    let s = *runtime_args.stack.last().ok_or(RuntimeError::StackEmpty)?;
+    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(operation.calc(a, s)?);
     Ok(())
 }
 
@@ -269,10 +1231,10 @@ fn calc_accumulator_with_memory_cells(runtime_args: &mut RuntimeArgs, operation:
 /// - p(j) = value of memory cell with label **label_b**
 /// - x = constant with value **value**
 /// - op = the operation to perform
-fn calc_memory_cell_with_memory_cell_constant(runtime_args: &mut RuntimeArgs, operation: &Operation, label_a: &str, label_b: &str, value: &i32) -> Result<(), String> {
+fn calc_memory_cell_with_memory_cell_constant(runtime_args: &mut RuntimeArgs, operation: &Operation, label_a: &str, label_b: &str, value: &i32) -> Result<(), RuntimeError> {
     assert_memory_cell_exists(runtime_args, label_a)?;
     let a = assert_memory_cell_contains_value(runtime_args, label_b)?;
-    runtime_args.memory_cells.get_mut(label_a).unwrap().data = Some(operation.calc(a, *value));
+    runtime_args.memory_cells.get_mut(label_a).unwrap().data = Some(operation.calc(a, *value)?);
     Ok(())
 }
 
@@ -282,11 +1244,11 @@ fn calc_memory_cell_with_memory_cell_constant(runtime_args: &mut RuntimeArgs, op
 /// - p(j) = value of memory cell with label **label_b**
 /// - a = value of accumulator with index **a_idx**
 /// - op = the operation to perform
-fn calc_memory_cell_with_memory_cell_accumulator(runtime_args: &mut RuntimeArgs, operation: &Operation, label_a: &str, label_b: &str, a_idx: &usize) -> Result<(), String> {
+fn calc_memory_cell_with_memory_cell_accumulator(runtime_args: &mut RuntimeArgs, operation: &Operation, label_a: &str, label_b: &str, a_idx: &usize) -> Result<(), RuntimeError> {
     assert_memory_cell_exists(runtime_args, label_a)?;
     let a = assert_memory_cell_contains_value(runtime_args, label_b)?;
     let b = assert_accumulator_contains_value(runtime_args, a_idx)?;
-    runtime_args.memory_cells.get_mut(label_a).unwrap().data = Some(operation.calc(a, b));
+    runtime_args.memory_cells.get_mut(label_a).unwrap().data = Some(operation.calc(a, b)?);
     Ok(())
 }
 
@@ -296,21 +1258,171 @@ fn calc_memory_cell_with_memory_cell_accumulator(runtime_args: &mut RuntimeArgs,
 /// - p(j) = value of memory cell with label **label_b**
 /// - p(k) = value of memory cell with label **label_c**
 /// - op = the operation to perform
-fn calc_memory_cell_with_memory_cells(runtime_args: &mut RuntimeArgs, operation: &Operation, label_a: &str, label_b: &str, label_c: &str) -> Result<(), String> {
+fn calc_memory_cell_with_memory_cells(runtime_args: &mut RuntimeArgs, operation: &Operation, label_a: &str, label_b: &str, label_c: &str) -> Result<(), RuntimeError> {
     assert_memory_cell_exists(runtime_args, label_a)?;
     let a = assert_memory_cell_contains_value(runtime_args, label_b)?;
     let b = assert_memory_cell_contains_value(runtime_args, label_c)?;
-    runtime_args.memory_cells.get_mut(label_a).unwrap().data = Some(operation.calc(a, b));
+    runtime_args.memory_cells.get_mut(label_a).unwrap().data = Some(operation.calc(a, b)?);
+    Ok(())
+}
+
+/// Runs code equal to **a := a + p(i) * p(j)**
+/// 
+/// - a = value of accumulator with index **a_idx**
+/// - p(i) = value of memory cell with label **label_a**
+/// - p(j) = value of memory cell with label **label_b**
+/// 
+/// Errors when the product or the sum does not fit into an i32, the accumulator is not modified in that case.
+fn mul_add(runtime_args: &mut RuntimeArgs, a_idx: &usize, label_a: &str, label_b: &str) -> Result<(), RuntimeError> {
+    let a = assert_accumulator_contains_value(runtime_args, a_idx)?;
+    let x = assert_memory_cell_contains_value(runtime_args, label_a)?;
+    let y = assert_memory_cell_contains_value(runtime_args, label_b)?;
+    let product = x.checked_mul(y).ok_or_else(|| RuntimeError::Overflow(format!("{} * {}", x, y)))?;
+    let sum = a.checked_add(product).ok_or_else(|| RuntimeError::Overflow(format!("{} + {}", a, product)))?;
+    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(sum);
+    Ok(())
+}
+
+/// Runs code equal to **a := |b - c|**
+/// 
+/// - a = value of accumulator with index **a_idx_a**
+/// - b = value of accumulator with index **a_idx_b**
+/// - c = value of accumulator with index **a_idx_c**
+/// 
+/// Errors when the result does not fit into an i32.
+fn abs_diff(runtime_args: &mut RuntimeArgs, a_idx_a: &usize, a_idx_b: &usize, a_idx_c: &usize) -> Result<(), RuntimeError> {
+    assert_accumulator_exists(runtime_args, a_idx_a)?;
+    let b = assert_accumulator_contains_value(runtime_args, a_idx_b)?;
+    let c = assert_accumulator_contains_value(runtime_args, a_idx_c)?;
+    let result = i32::try_from(b.abs_diff(c)).map_err(|_| RuntimeError::Overflow(format!("|{} - {}|", b, c)))?;
+    runtime_args.accumulators.get_mut(*a_idx_a).unwrap().data = Some(result);
+    Ok(())
+}
+
+/// Runs code equal to **a := f(a)**
+/// 
+/// - a = value of accumulator with index **a_idx**
+/// - f = the transform to apply
+/// 
+/// Errors when the result does not fit into an i32.
+fn transform_accumulator(runtime_args: &mut RuntimeArgs, a_idx: &usize, transform: &Transform) -> Result<(), RuntimeError> {
+    let a = assert_accumulator_contains_value(runtime_args, a_idx)?;
+    let result = transform.apply(a).ok_or_else(|| RuntimeError::Overflow(format!("{}({})", transform, a)))?;
+    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(result);
+    Ok(())
+}
+
+/// Runs code equal to **a := gcd(b, c)**
+/// 
+/// - a = value of accumulator with index **a_idx_a**
+/// - b = value of accumulator with index **a_idx_b**
+/// - c = value of accumulator with index **a_idx_c**
+/// 
+/// The result is never negative, gcd(x, 0) is |x| and gcd(0, 0) is 0.
+/// Errors when the result does not fit into an i32, which only happens for gcd(i32::MIN, 0), gcd(0, i32::MIN) and gcd(i32::MIN, i32::MIN).
+fn gcd(runtime_args: &mut RuntimeArgs, a_idx_a: &usize, a_idx_b: &usize, a_idx_c: &usize) -> Result<(), RuntimeError> {
+    assert_accumulator_exists(runtime_args, a_idx_a)?;
+    let b = assert_accumulator_contains_value(runtime_args, a_idx_b)?;
+    let c = assert_accumulator_contains_value(runtime_args, a_idx_c)?;
+    let (mut x, mut y) = (b.unsigned_abs(), c.unsigned_abs());
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    let result = i32::try_from(x).map_err(|_| RuntimeError::Overflow(format!("gcd({}, {})", b, c)))?;
+    runtime_args.accumulators.get_mut(*a_idx_a).unwrap().data = Some(result);
+    Ok(())
+}
+
+/// Runs code equal to **a := popcount(b)**
+/// 
+/// - a = value of accumulator with index **a_idx_a**, receives the number of bits that are set in **b**
+/// - b = value of accumulator with index **a_idx_b**
+/// 
+/// Negative values are counted in two's complement, so popcount(-1) is 32.
+fn pop_count(runtime_args: &mut RuntimeArgs, a_idx_a: &usize, a_idx_b: &usize) -> Result<(), RuntimeError> {
+    assert_accumulator_exists(runtime_args, a_idx_a)?;
+    let b = assert_accumulator_contains_value(runtime_args, a_idx_b)?;
+    runtime_args.accumulators.get_mut(*a_idx_a).unwrap().data = Some(b.count_ones() as i32);
+    Ok(())
+}
+
+/// Runs code equal to **a := min(p(base0), ..., p(base(n-1)))**
+/// 
+/// - a = value of accumulator with index **a_idx**
+/// - base = label of the memory cells without the numeric suffix, given by **base_label**
+/// - n = number of memory cells, given by **count**
+/// 
+/// Errors when one of the memory cells is missing or empty or when **count** is 0,
+/// in that case the range does not contain the memory cell base0.
+fn min_of_cells(runtime_args: &mut RuntimeArgs, a_idx: &usize, base_label: &str, count: &usize) -> Result<(), RuntimeError> {
+    assert_accumulator_exists(runtime_args, a_idx)?;
+    let min = memory_cell_range_values(runtime_args, base_label, *count)?.into_iter().min()
+        .ok_or_else(|| RuntimeError::MemoryCellDoesNotExist(format!("{}0", base_label)))?;
+    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(min);
+    Ok(())
+}
+
+/// Runs code equal to **a := max(p(base0), ..., p(base(n-1)))**
+/// 
+/// - a = value of accumulator with index **a_idx**
+/// - base = label of the memory cells without the numeric suffix, given by **base_label**
+/// - n = number of memory cells, given by **count**
+/// 
+/// Errors when one of the memory cells is missing or empty or when **count** is 0,
+/// in that case the range does not contain the memory cell base0.
+fn max_of_cells(runtime_args: &mut RuntimeArgs, a_idx: &usize, base_label: &str, count: &usize) -> Result<(), RuntimeError> {
+    assert_accumulator_exists(runtime_args, a_idx)?;
+    let max = memory_cell_range_values(runtime_args, base_label, *count)?.into_iter().max()
+        .ok_or_else(|| RuntimeError::MemoryCellDoesNotExist(format!("{}0", base_label)))?;
+    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(max);
+    Ok(())
+}
+
+/// Runs code equal to **a := p(base0) + ... + p(base(n-1))**
+/// 
+/// - a = value of accumulator with index **a_idx**
+/// - base = label of the memory cells without the numeric suffix, given by **base_label**
+/// - n = number of memory cells, given by **count**, the sum is 0 when **count** is 0
+/// 
+/// Errors when one of the memory cells is missing or empty or when the sum does not fit into an i32.
+fn sum_cells(runtime_args: &mut RuntimeArgs, a_idx: &usize, base_label: &str, count: &usize) -> Result<(), RuntimeError> {
+    assert_accumulator_exists(runtime_args, a_idx)?;
+    let mut sum: i32 = 0;
+    for value in memory_cell_range_values(runtime_args, base_label, *count)? {
+        sum = sum.checked_add(value).ok_or_else(|| RuntimeError::Overflow(format!("{} + {}", sum, value)))?;
+    }
+    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(sum);
+    Ok(())
+}
+
+/// Runs code equal to **a := a rotl n**
+/// 
+/// - a = value of accumulator with index **a_idx**
+/// - n = number of bits the value is rotated to the left, bits that are shifted out on the left are inserted on the right
+fn rotate_left(runtime_args: &mut RuntimeArgs, a_idx: &usize, n: &u32) -> Result<(), RuntimeError> {
+    let a = assert_accumulator_contains_value(runtime_args, a_idx)?;
+    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(a.rotate_left(*n));
+    Ok(())
+}
+
+/// Runs code equal to **a := a rotr n**
+/// 
+/// - a = value of accumulator with index **a_idx**
+/// - n = number of bits the value is rotated to the right, bits that are shifted out on the right are inserted on the left
+fn rotate_right(runtime_args: &mut RuntimeArgs, a_idx: &usize, n: &u32) -> Result<(), RuntimeError> {
+    let a = assert_accumulator_contains_value(runtime_args, a_idx)?;
+    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(a.rotate_right(*n));
     Ok(())
 }
 
 /// Runs code equal to **goto label**
 /// 
 /// - label = label to which to jump
+/// - linked_index = index **label** has been resolved to when the program is linked
 /// 
 /// Sets the next instruction index to index contained behind **label** in [instruction_labels](../runtime/struct.ControlFlow.html#structfield.instruction_labels) map.
-fn goto(runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFlow, label: &str) -> Result<(), String> {
-    control_flow.next_instruction_index(label)?;
+fn goto(_runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFlow, label: &str, linked_index: Option<usize>) -> Result<(), RuntimeError> {
+    jump(control_flow, label, linked_index)?;
     Ok(())
 }
 
@@ -319,11 +1431,11 @@ fn goto(runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFlow, label: &
 /// - b = value of accumulator with index **a_idx_b**
 /// - label = label to which to jump
 /// - cmp = the way how **a** and **b** should be compared
-fn goto_if_accumulator(runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFlow, comparison: &Comparison, label: &str, a_idx_a: &usize, a_idx_b: &usize) -> Result<(), String> {
+fn goto_if_accumulator(runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFlow, comparison: &Comparison, label: &str, a_idx_a: &usize, a_idx_b: &usize, linked_index: Option<usize>) -> Result<(), RuntimeError> {
     let a = assert_accumulator_contains_value(runtime_args, a_idx_a)?;
     let b = assert_accumulator_contains_value(runtime_args, a_idx_b)?;
     if comparison.cmp(a, b) {
-        control_flow.next_instruction_index(label)?
+        jump(control_flow, label, linked_index)?
     }
     Ok(())
 }
@@ -333,10 +1445,10 @@ fn goto_if_accumulator(runtime_args: &mut RuntimeArgs, control_flow: &mut Contro
 /// - x = constant with value **value**
 /// - label = label to which to jump
 /// - cmp = the way how **a** and **x** should be compared
-fn goto_if_constant(runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFlow, comparison: &Comparison, label: &str, a_idx: &usize, c: &i32) -> Result<(), String> {
+fn goto_if_constant(runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFlow, comparison: &Comparison, label: &str, a_idx: &usize, c: &i32, linked_index: Option<usize>) -> Result<(), RuntimeError> {
     let a = assert_accumulator_contains_value(runtime_args, a_idx)?;
     if comparison.cmp(a, *c) {
-        control_flow.next_instruction_index(label)?
+        jump(control_flow, label, linked_index)?
     }
     Ok(())
 }
@@ -346,100 +1458,303 @@ fn goto_if_constant(runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFl
 /// - p(i) = value of memory cell with label **mcl**
 /// - label = label to which to jump
 /// - cmp = the way how **a** and **x** should be compared
-fn goto_if_memory_cell(runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFlow, comparison: &Comparison, label: &str, a_idx: &usize, mcl: &str) -> Result<(), String> {
+fn goto_if_memory_cell(runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFlow, comparison: &Comparison, label: &str, a_idx: &usize, mcl: &str, linked_index: Option<usize>) -> Result<(), RuntimeError> {
     let a = assert_accumulator_contains_value(runtime_args, a_idx)?;
     let b = assert_memory_cell_contains_value(runtime_args, mcl)?;
     if comparison.cmp(a, b) {
-        control_flow.next_instruction_index(label)?
+        jump(control_flow, label, linked_index)?
+    }
+    Ok(())
+}
+
+/// Runs code equal to **if p(i) cmp x then goto label**
+/// - p(i) = value of memory cell with label **mcl**
+/// - x = constant with value **c**
+/// - label = label to which to jump
+/// - cmp = the way how **p(i)** and **x** should be compared
+fn goto_if_memory_cell_constant(runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFlow, comparison: &Comparison, label: &str, mcl: &str, c: &i32, linked_index: Option<usize>) -> Result<(), RuntimeError> {
+    let a = assert_memory_cell_contains_value(runtime_args, mcl)?;
+    if comparison.cmp(a, *c) {
+        jump(control_flow, label, linked_index)?
+    }
+    Ok(())
+}
+
+/// Runs code equal to **if a % x == 0 then goto label**
+/// - a = value of accumulator with index **a_idx**
+/// - x = constant with value **divisor**
+/// - label = label to which to jump
+/// 
+/// Errors when **divisor** is 0.
+fn goto_if_divisible(runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFlow, a_idx: &usize, divisor: &i32, label: &str, linked_index: Option<usize>) -> Result<(), RuntimeError> {
+    let a = assert_accumulator_contains_value(runtime_args, a_idx)?;
+    if *divisor == 0 {
+        return Err(RuntimeError::DivisionByZero);
+    }
+    if a.wrapping_rem(*divisor) == 0 {
+        jump(control_flow, label, linked_index)?
+    }
+    Ok(())
+}
+
+/// Runs code equal to **if pop cmp x then goto label**
+/// - pop = value that is removed from the top of the stack
+/// - x = constant with value **c**
+/// - label = label to which to jump
+/// - cmp = the way how **pop** and **x** should be compared
+/// 
+/// The value is removed from the stack even when the jump is not taken.
+fn goto_if_stack_pop(runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFlow, comparison: &Comparison, label: &str, c: &i32, linked_index: Option<usize>) -> Result<(), RuntimeError> {
+    let value = runtime_args.stack.pop().ok_or(RuntimeError::StackEmpty)?;
+    if comparison.cmp(value, *c) {
+        jump(control_flow, label, linked_index)?
+    }
+    Ok(())
+}
+
+/// Runs code equal to **if s cmp p(i) then goto label**
+/// - s = value on top of the stack, the stack is not modified
+/// - p(i) = value of memory cell with label **mcl**
+/// - label = label to which to jump
+/// - cmp = the way how **s** and **p(i)** should be compared
+fn goto_if_stack_top_memory_cell(runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFlow, comparison: &Comparison, label: &str, mcl: &str, linked_index: Option<usize>) -> Result<(), RuntimeError> {
+    let s = *runtime_args.stack.last().ok_or(RuntimeError::StackEmpty)?;
+    let b = assert_memory_cell_contains_value(runtime_args, mcl)?;
+    if comparison.cmp(s, b) {
+        jump(control_flow, label, linked_index)?
+    }
+    Ok(())
+}
+
+/// Runs code equal to **a := address of label**
+/// - a = value of accumulator with index **a_idx**
+/// - label = label whose instruction index is stored
+/// 
+/// The stored index can be used with [goto_accumulator](fn.goto_accumulator.html).
+fn load_label_address(runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFlow, a_idx: &usize, label: &str) -> Result<(), RuntimeError> {
+    assert_accumulator_exists(runtime_args, a_idx)?;
+    let index = control_flow.label_index(label).ok_or_else(|| RuntimeError::LabelMissing(label.to_string()))?;
+    let index = i32::try_from(index).map_err(|_| RuntimeError::Overflow(format!("address of {}", label)))?;
+    runtime_args.accumulators.get_mut(*a_idx).unwrap().data = Some(index);
+    Ok(())
+}
+
+/// Runs code equal to **goto a**
+/// - a = value of accumulator with index **a_idx**, the index of the instruction to jump to
+/// 
+/// Errors when **a** is not the index of a label, see [load_label_address](fn.load_label_address.html).
+fn goto_accumulator(runtime_args: &mut RuntimeArgs, control_flow: &mut ControlFlow, a_idx: &usize) -> Result<(), RuntimeError> {
+    let a = assert_accumulator_contains_value(runtime_args, a_idx)?;
+    match usize::try_from(a) {
+        Ok(index) if control_flow.instruction_labels.values().any(|label_index| *label_index == index) => {
+            control_flow.next_instruction_index = index;
+            Ok(())
+        },
+        _ => Err(RuntimeError::InvalidIndex(a)),
     }
+}
+
+/// Runs code equal to **call label**
+/// - label = label of the subroutine to which to jump
+/// 
+/// The index of the next instruction is stored on the call stack, so that [return_from_call](fn.return_from_call.html) can jump back to it.
+fn call(control_flow: &mut ControlFlow, label: &str, linked_index: Option<usize>) -> Result<(), RuntimeError> {
+    let return_index = control_flow.next_instruction_index;
+    jump(control_flow, label, linked_index)?;
+    control_flow.call_stack.push(return_index);
+    Ok(())
+}
+
+/// Runs code equal to **return**
+/// 
+/// Jumps back to the instruction after the last [call](fn.call.html).
+fn return_from_call(control_flow: &mut ControlFlow) -> Result<(), RuntimeError> {
+    control_flow.next_instruction_index = control_flow.call_stack.pop().ok_or(RuntimeError::CallStackEmpty)?;
     Ok(())
 }
 
-/// Tests if the accumulator with **index** exists.
-fn assert_accumulator_exists(runtime_args: &mut RuntimeArgs, index: &usize) -> Result<(), String> {
+/// Removes the values of all accumulators and memory cells and clears the stack.
+/// 
+/// The accumulators and memory cells are kept.
+/// Accumulators above the [accumulator_soft_limit](../runtime/struct.RuntimeArgs.html#structfield.accumulator_soft_limit) are not changed.
+fn reset_state(runtime_args: &mut RuntimeArgs) {
+    let count = runtime_args.accessible_accumulators();
+    for accumulator in runtime_args.accumulators[..count].iter_mut() {
+        accumulator.data = None;
+    }
+    for memory_cell in runtime_args.memory_cells.values_mut() {
+        memory_cell.data = None;
+    }
+    runtime_args.stack.clear();
+}
+
+/// Returns the labels **base0**, ..., **base(count-1)** of a range of memory cells.
+fn memory_cell_range_labels(base_label: &str, count: usize) -> Vec<String> {
+    (0..count).map(|i| format!("{}{}", base_label, i)).collect()
+}
+
+/// Returns the values of the memory cells **base0**, ..., **base(count-1)**,
+/// errors when one of the memory cells is missing or empty.
+fn memory_cell_range_values(runtime_args: &mut RuntimeArgs, base_label: &str, count: usize) -> Result<Vec<i32>, RuntimeError> {
+    memory_cell_range_labels(base_label, count).iter()
+        .map(|label| assert_memory_cell_contains_value(runtime_args, label))
+        .collect()
+}
+
+/// Tests if the accumulator with **index** exists and is not above the [accumulator_soft_limit](../runtime/struct.RuntimeArgs.html#structfield.accumulator_soft_limit).
+fn assert_accumulator_exists(runtime_args: &mut RuntimeArgs, index: &usize) -> Result<(), RuntimeError> {
+    assert_accumulator_within_soft_limit(runtime_args, index)?;
     if let Some(_value) = runtime_args.accumulators.get(*index) {
         Ok(())
     } else {
-        Err(format!("Accumulator with index {} does not exist!", index))
+        Err(RuntimeError::AccumulatorDoesNotExist(*index))
     }
 }
 
 /// Tests if the accumulator with **index** exists and contains a value.
 /// 
-/// Ok(i32) contains the accumulator value.
+/// Ok(i32) contains the accumulator value, or 0 when the accumulator is empty and [treat_empty_as_zero](../runtime/struct.RuntimeArgs.html#structfield.treat_empty_as_zero) is set.
 /// 
-/// Err(String) contains error message.
-fn assert_accumulator_contains_value(runtime_args: &mut RuntimeArgs, index: &usize) -> Result<i32, String> {
+/// Err(RuntimeError) contains the reason why the value could not be read.
+fn assert_accumulator_contains_value(runtime_args: &mut RuntimeArgs, index: &usize) -> Result<i32, RuntimeError> {
+    assert_accumulator_within_soft_limit(runtime_args, index)?;
     if let Some(value) = runtime_args.accumulators.get(*index) {
         if value.data.is_some() {
             Ok(runtime_args.accumulators.get(*index).unwrap().data.unwrap())
+        } else if runtime_args.treat_empty_as_zero {
+            runtime_args.uninitialized_reads.push(format!("a{}", index));
+            Ok(0)
         } else {
-            Err(format!("Accumulator with index {} does not contain data!", index))
+            Err(RuntimeError::AccumulatorUninitialized(*index))
         }
     } else {
-        Err(format!("Accumulator with index {} does not exist!", index))
+        Err(RuntimeError::AccumulatorDoesNotExist(*index))
+    }
+}
+
+/// Tests if **index** is not above the [accumulator_soft_limit](../runtime/struct.RuntimeArgs.html#structfield.accumulator_soft_limit).
+fn assert_accumulator_within_soft_limit(runtime_args: &RuntimeArgs, index: &usize) -> Result<(), RuntimeError> {
+    match runtime_args.accumulator_soft_limit {
+        Some(limit) if *index > limit => Err(RuntimeError::AccumulatorAboveSoftLimit(*index, limit)),
+        _ => Ok(()),
     }
 }
 
 /// Tests if the memory cell with **label** exists.
-fn assert_memory_cell_exists(runtime_args: &mut RuntimeArgs, label: &str) -> Result<(), String> {
+fn assert_memory_cell_exists(runtime_args: &mut RuntimeArgs, label: &str) -> Result<(), RuntimeError> {
     if let Some(_value) = runtime_args.memory_cells.get(label) {
         Ok(())
     } else {
-        Err(format!("Memory cell with label {} does not exist!", label))
+        Err(RuntimeError::MemoryCellDoesNotExist(label.to_string()))
     }
 }
 
 /// Tests if the memory cell with **label** exists and contains a value.
 /// 
-/// Ok(i32) contains the memory cell value.
+/// Ok(i32) contains the memory cell value, or 0 when the memory cell is empty and [treat_empty_as_zero](../runtime/struct.RuntimeArgs.html#structfield.treat_empty_as_zero) is set.
 /// 
-/// Err(String) contains error message.
-fn assert_memory_cell_contains_value(runtime_args: &mut RuntimeArgs, label: &str) -> Result<i32, String> {
+/// Err(RuntimeError) contains the reason why the value could not be read.
+fn assert_memory_cell_contains_value(runtime_args: &mut RuntimeArgs, label: &str) -> Result<i32, RuntimeError> {
     if let Some(value) = runtime_args.memory_cells.get(label) {
         if value.data.is_some() {
             Ok(runtime_args.memory_cells.get(label).unwrap().data.unwrap())
+        } else if runtime_args.treat_empty_as_zero {
+            runtime_args.uninitialized_reads.push(format!("p({})", label));
+            Ok(0)
         } else {
-            Err(format!("Memory cell with label {} does not contain data!", label))
+            Err(RuntimeError::MemoryCellUninitialized(label.to_string()))
         }
     } else {
-        Err(format!("Memory cell with label {} does not exist!", label))
+        Err(RuntimeError::MemoryCellDoesNotExist(label.to_string()))
+    }
+}
+
+/// Sets the next instruction index to **linked_index** if it is set, otherwise to the index of **label**.
+/// 
+/// See [next_instruction_index](../runtime/struct.ControlFlow.html#method.next_instruction_index)
+fn jump(control_flow: &mut ControlFlow, label: &str, linked_index: Option<usize>) -> Result<(), RuntimeError> {
+    match linked_index {
+        Some(index) => {
+            control_flow.next_instruction_index = index;
+            Ok(())
+        },
+        None => control_flow.next_instruction_index(label),
     }
 }
 
 /// Prints the current contents of the accumulators into the console
-fn print_accumulators(runtime_args: &RuntimeArgs) {
-    println!("--- Accumulators ---");
+/// 
+/// Does nothing when [quiet](../runtime/struct.RuntimeArgs.html#structfield.quiet) is set.
+fn print_accumulators(runtime_args: &mut RuntimeArgs) {
+    if runtime_args.quiet {
+        return;
+    }
+    let mut lines = vec![String::from("--- Accumulators ---")];
     for (index, i) in runtime_args.accumulators.iter().enumerate() {
-        println!("{} - {:?}", index, i.data);
+        lines.push(format!("{} - {:?}", index, i.data));
     }
-    println!("--------------------");
+    lines.push(String::from("--------------------"));
+    output(runtime_args, lines);
 }
 
 /// Prints the current contents of the memory cells into the console
-fn print_memory_cells(runtime_args: &RuntimeArgs) {
+/// 
+/// Does nothing when [quiet](../runtime/struct.RuntimeArgs.html#structfield.quiet) is set.
+fn print_memory_cells(runtime_args: &mut RuntimeArgs) {
+    if runtime_args.quiet {
+        return;
+    }
     // TODO Make print sorted (Alpabetically by label name)
-    println!("--- Memory Cells ---");
+    let mut lines = vec![String::from("--- Memory Cells ---")];
     for (k, v) in &runtime_args.memory_cells {
-        println!("{} - {:?}", k, v.data);
+        lines.push(format!("{} - {:?}", k, v.data));
     }
-    println!("--------------------");
+    lines.push(String::from("--------------------"));
+    output(runtime_args, lines);
 }
 
 /// Prints the current layout of the stack into the console
-fn print_stack(runtime_args: &RuntimeArgs) {
-    println!("------ Stack -------");
+/// 
+/// Does nothing when [quiet](../runtime/struct.RuntimeArgs.html#structfield.quiet) is set.
+fn print_stack(runtime_args: &mut RuntimeArgs) {
+    if runtime_args.quiet {
+        return;
+    }
+    let mut lines = vec![String::from("------ Stack -------")];
     for (index, i) in runtime_args.stack.iter().enumerate() {
-        println!("{} - {:?}", index, i);
+        lines.push(format!("{} - {:?}", index, i));
+    }
+    lines.push(String::from("--------------------"));
+    output(runtime_args, lines);
+}
+
+/// Prints **message** followed by the value of the accumulator with index **a_idx**, formatted as **message: value**.
+/// 
+/// Does nothing when [quiet](../runtime/struct.RuntimeArgs.html#structfield.quiet) is set, but still errors when the accumulator is empty.
+fn print_labeled(runtime_args: &mut RuntimeArgs, message: &str, a_idx: &usize) -> Result<(), RuntimeError> {
+    let value = assert_accumulator_contains_value(runtime_args, a_idx)?;
+    if !runtime_args.quiet {
+        output(runtime_args, vec![format!("{}: {}", message, value)]);
+    }
+    Ok(())
+}
+
+/// Prints **lines** into the console or adds them to [captured_output](../runtime/struct.RuntimeArgs.html#structfield.captured_output) when output is captured.
+fn output(runtime_args: &mut RuntimeArgs, lines: Vec<String>) {
+    if let Some(captured_output) = &mut runtime_args.captured_output {
+        captured_output.extend(lines);
+    } else {
+        for line in lines {
+            println!("{}", line);
+        }
     }
-    println!("--------------------");
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use crate::{runtime::{ControlFlow, RuntimeArgs, Runner}, instructions::Instruction, base::{Accumulator, MemoryCell, Comparison, Operation}};
+    use crate::{runtime::{ControlFlow, RuntimeArgs, Runner}, instructions::Instruction, base::{Accumulator, MemoryCell, Comparison, Operation, RuntimeError, Transform}};
 
     
     #[test]
@@ -495,11 +1810,11 @@ mod tests {
         args.accumulators = vec![Accumulator::new(0)];
         let err = Instruction::AssignAccumulatorValueFromMemoryCell(0, "a").run(&mut args, &mut control_flow);
         assert!(err.is_err());
-        assert!(err.err().unwrap().contains("Memory cell"));
+        assert!(err.err().unwrap().to_string().contains("Memory cell"));
         args.memory_cells.insert("a", MemoryCell::new("a"));
         let err = Instruction::AssignAccumulatorValueFromMemoryCell(1, "a").run(&mut args, &mut control_flow);
         assert!(err.is_err());
-        assert!(err.err().unwrap().contains("Accumulator"));
+        assert!(err.err().unwrap().to_string().contains("Accumulator"));
     }
     
     #[test]
@@ -535,11 +1850,11 @@ mod tests {
         args.accumulators = vec![Accumulator::new(0)];
         let err = Instruction::AssignMemoryCellValueFromAccumulator("a", 0).run(&mut args, &mut control_flow);
         assert!(err.is_err());
-        assert!(err.err().unwrap().contains("Memory cell"));
+        assert!(err.err().unwrap().to_string().contains("Memory cell"));
         args.memory_cells.insert("a", MemoryCell::new("a"));
         let err = Instruction::AssignMemoryCellValueFromAccumulator("a", 1).run(&mut args, &mut control_flow);
         assert!(err.is_err());
-        assert!(err.err().unwrap().contains("Accumulator"));
+        assert!(err.err().unwrap().to_string().contains("Accumulator"));
     }
 
     #[test]
@@ -772,6 +2087,601 @@ mod tests {
         assert_eq!(runner.runtime_args().accumulators[0].data.unwrap(), 256);
     }
 
+    #[test]
+    fn test_stack_negate() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        assert!(Instruction::StackNegate().run(&mut args, &mut control_flow).is_err());
+        args.stack.push(5);
+        Instruction::StackNegate().run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.stack, vec![-5]);
+        args.stack = vec![i32::MIN];
+        assert!(Instruction::StackNegate().run(&mut args, &mut control_flow).is_err());
+    }
+
+    #[test]
+    fn test_effects() {
+        let effects = Instruction::CalcMemoryCellWithMemoryCellAccumulator(Operation::Plus, "a", "b", 1).effects();
+        assert_eq!(effects.read_accumulators, vec![1]);
+        assert!(effects.written_accumulators.is_empty());
+        assert_eq!(effects.read_memory_cells, vec!["b"]);
+        assert_eq!(effects.written_memory_cells, vec!["a"]);
+    }
+
+    #[test]
+    fn test_memory_cell_labels() {
+        assert_eq!(Instruction::CalcMemoryCellWithMemoryCells(Operation::Plus, "a", "b", "c").memory_cell_labels(), vec!["a", "b", "c"]);
+        assert_eq!(Instruction::GotoIfMemoryCell(Comparison::Equal, "loop", 0, "d").memory_cell_labels(), vec!["d"]);
+        assert!(Instruction::SumCells(0, "s", 3).memory_cell_labels().is_empty());
+        assert!(Instruction::Goto("loop").memory_cell_labels().is_empty());
+    }
+
+    #[test]
+    fn test_assert_stack_empty() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        assert!(Instruction::AssertStackEmpty().run(&mut args, &mut control_flow).is_ok());
+        args.stack = vec![1, 2];
+        let err = Instruction::AssertStackEmpty().run(&mut args, &mut control_flow);
+        assert!(err.err().unwrap().to_string().contains("2 values"));
+    }
+
+    #[test]
+    fn test_store_accumulators() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.memory_cells.insert("h0", MemoryCell::new("h0"));
+        args.memory_cells.insert("h1", MemoryCell::new("h1"));
+        Instruction::AssignAccumulatorValue(0, 5).run(&mut args, &mut control_flow).unwrap();
+        Instruction::AssignAccumulatorValue(1, 7).run(&mut args, &mut control_flow).unwrap();
+        Instruction::StoreAccumulators(2, "h").run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.memory_cells.get("h0").unwrap().data.unwrap(), 5);
+        assert_eq!(args.memory_cells.get("h1").unwrap().data.unwrap(), 7);
+        assert!(Instruction::StoreAccumulators(3, "h").run(&mut args, &mut control_flow).is_err());
+    }
+
+    #[test]
+    fn test_push_constant() {
+        let mut args = setup_empty_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        Instruction::PushConstant(7).run(&mut args, &mut control_flow).unwrap();
+        Instruction::PushConstant(8).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.stack, vec![7, 8]);
+    }
+
+    #[test]
+    fn test_call_return() {
+        let mut args = setup_empty_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        control_flow.instruction_labels.insert("sub", 10);
+        control_flow.next_instruction_index = 3;
+        Instruction::Call("sub").run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(control_flow.next_instruction_index, 10);
+        Instruction::Return().run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(control_flow.next_instruction_index, 3);
+        assert!(Instruction::Return().run(&mut args, &mut control_flow).is_err());
+        assert!(Instruction::Call("missing").run(&mut args, &mut control_flow).is_err());
+        assert!(control_flow.call_stack.is_empty());
+    }
+
+    #[test]
+    fn test_quiet() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.quiet = true;
+        args.stack.push(1);
+        assert!(Instruction::PrintAccumulators().run(&mut args, &mut control_flow).is_ok());
+        assert!(Instruction::PrintMemoryCells().run(&mut args, &mut control_flow).is_ok());
+        assert!(Instruction::PrintStack().run(&mut args, &mut control_flow).is_ok());
+        args.captured_output = Some(Vec::new());
+        Instruction::PrintStack().run(&mut args, &mut control_flow).unwrap();
+        assert!(args.captured_output.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_goto_if_divisible() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        control_flow.instruction_labels.insert("loop", 20);
+        Instruction::AssignAccumulatorValue(0, 12).run(&mut args, &mut control_flow).unwrap();
+        Instruction::GotoIfDivisible(0, 5, "loop").run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(control_flow.next_instruction_index, 0);
+        Instruction::GotoIfDivisible(0, 4, "loop").run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(control_flow.next_instruction_index, 20);
+        assert_eq!(Instruction::GotoIfDivisible(0, 0, "loop").run(&mut args, &mut control_flow), Err(RuntimeError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_stack_len() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        Instruction::StackLen(0).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data.unwrap(), 0);
+        for i in 0..3 {
+            Instruction::PushConstant(i).run(&mut args, &mut control_flow).unwrap();
+        }
+        Instruction::StackLen(0).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data.unwrap(), 3);
+    }
+
+    #[test]
+    fn test_goto_if_memory_cell_constant() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        control_flow.instruction_labels.insert("loop", 20);
+        assert!(Instruction::GotoIfMemoryCellConstant(Comparison::Less, "loop", "a", 40).run(&mut args, &mut control_flow).is_err());
+        Instruction::AssignMemoryCellValue("a", 20).run(&mut args, &mut control_flow).unwrap();
+        Instruction::GotoIfMemoryCellConstant(Comparison::More, "loop", "a", 40).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(control_flow.next_instruction_index, 0);
+        Instruction::GotoIfMemoryCellConstant(Comparison::Less, "loop", "a", 40).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(control_flow.next_instruction_index, 20);
+    }
+
+    #[test]
+    fn test_spill_stack() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.stack = vec![1, 2, 3];
+        assert!(Instruction::SpillStack("s").run(&mut args, &mut control_flow).is_err());
+        for label in ["s0", "s1", "s2"] {
+            args.memory_cells.insert(label, MemoryCell::new(label));
+        }
+        Instruction::SpillStack("s").run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.memory_cells.get("s0").unwrap().data.unwrap(), 1);
+        assert_eq!(args.memory_cells.get("s1").unwrap().data.unwrap(), 2);
+        assert_eq!(args.memory_cells.get("s2").unwrap().data.unwrap(), 3);
+        assert_eq!(args.stack, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_push_pop_all_accumulators() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        Instruction::AssignAccumulatorValue(0, 1).run(&mut args, &mut control_flow).unwrap();
+        Instruction::AssignAccumulatorValue(1, 2).run(&mut args, &mut control_flow).unwrap();
+        Instruction::AssignAccumulatorValue(2, 3).run(&mut args, &mut control_flow).unwrap();
+        Instruction::PushAllAccumulators().run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.stack, vec![1, 2, 3]);
+        Instruction::AssignAccumulatorValue(0, 10).run(&mut args, &mut control_flow).unwrap();
+        Instruction::AssignAccumulatorValue(2, 30).run(&mut args, &mut control_flow).unwrap();
+        Instruction::PopAllAccumulators().run(&mut args, &mut control_flow).unwrap();
+        let values: Vec<Option<i32>> = args.accumulators.iter().map(|a| a.data).collect();
+        assert_eq!(values, vec![Some(1), Some(2), Some(3)]);
+        assert!(args.stack.is_empty());
+        args.stack = vec![1, 2];
+        assert_eq!(Instruction::PopAllAccumulators().run(&mut args, &mut control_flow), Err(RuntimeError::StackTooSmall(3, 2)));
+        assert_eq!(args.stack, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_stack_rotate() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.stack = vec![1, 2, 3];
+        Instruction::StackRotate(3).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.stack, vec![3, 1, 2]);
+        Instruction::StackRotate(2).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.stack, vec![3, 2, 1]);
+        Instruction::StackRotate(0).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.stack, vec![3, 2, 1]);
+        assert_eq!(Instruction::StackRotate(4).run(&mut args, &mut control_flow), Err(RuntimeError::StackTooSmall(4, 3)));
+    }
+
+    #[test]
+    fn test_assign_if() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        Instruction::AssignAccumulatorValue(1, 5).run(&mut args, &mut control_flow).unwrap();
+        Instruction::AssignIf(0, Comparison::Less, 1, 10, 1, -1).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data.unwrap(), 1);
+        Instruction::AssignIf(0, Comparison::More, 1, 10, 1, -1).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data.unwrap(), -1);
+        assert!(Instruction::AssignIf(0, Comparison::More, 2, 10, 1, -1).run(&mut args, &mut control_flow).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Instruction::AssignAccumulatorValue(0, 5).to_string(), "a0 := 5");
+        assert_eq!(Instruction::CalcMemoryCellWithMemoryCellAccumulator(Operation::Minus, "a", "b", 1).to_string(), "p(a) := p(b) - a1");
+        assert_eq!(Instruction::GotoIfConstant(Comparison::LessOrEqual, "loop", 2, 10).to_string(), "if a2 <= 10 then goto loop");
+        assert_eq!(Instruction::StoreAccumulators(3, "h").to_string(), "p(h0), ..., p(h2) := a0, ..., a2");
+    }
+
+    #[test]
+    fn test_mul_add() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.accumulators[0].data = Some(0);
+        args.memory_cells.get_mut("a").unwrap().data = Some(3);
+        args.memory_cells.get_mut("b").unwrap().data = Some(4);
+        Instruction::MulAdd(0, "a", "b").run(&mut args, &mut control_flow).unwrap();
+        args.memory_cells.get_mut("a").unwrap().data = Some(-2);
+        args.memory_cells.get_mut("b").unwrap().data = Some(5);
+        Instruction::MulAdd(0, "a", "b").run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data.unwrap(), 2);
+        args.memory_cells.get_mut("a").unwrap().data = Some(i32::MAX);
+        assert_eq!(Instruction::MulAdd(0, "a", "b").run(&mut args, &mut control_flow), Err(RuntimeError::Overflow(format!("{} * 5", i32::MAX))));
+        assert_eq!(args.accumulators[0].data.unwrap(), 2);
+    }
+
+    #[test]
+    fn test_calc_accumulator_with_stack_top() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.accumulators[0].data = Some(5);
+        assert_eq!(Instruction::CalcAccumulatorWithStackTop(Operation::Plus, 0).run(&mut args, &mut control_flow), Err(RuntimeError::StackEmpty));
+        args.stack = vec![1, 3];
+        Instruction::CalcAccumulatorWithStackTop(Operation::Plus, 0).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(8));
+        assert_eq!(args.stack, vec![1, 3]);
+        args.stack.push(0);
+        assert_eq!(Instruction::CalcAccumulatorWithStackTop(Operation::Division, 0).run(&mut args, &mut control_flow), Err(RuntimeError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_store_stack_top() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        assert_eq!(Instruction::StoreStackTop("a").run(&mut args, &mut control_flow), Err(RuntimeError::StackEmpty));
+        args.stack = vec![4, 9];
+        Instruction::StoreStackTop("a").run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.memory_cells.get("a").unwrap().data, Some(9));
+        assert_eq!(args.stack, vec![4, 9]);
+        assert_eq!(Instruction::StoreStackTop("z").run(&mut args, &mut control_flow), Err(RuntimeError::MemoryCellDoesNotExist(String::from("z"))));
+    }
+
+    #[test]
+    fn test_pop_n() {
+        let mut args = setup_empty_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.stack = vec![1, 2, 3];
+        Instruction::PopN(2).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.stack, vec![1]);
+        assert_eq!(Instruction::PopN(2).run(&mut args, &mut control_flow), Err(RuntimeError::StackTooSmall(2, 1)));
+        assert_eq!(args.stack, vec![1]);
+    }
+
+    #[test]
+    fn test_describe() {
+        let mut args = setup_runtime_args();
+        args.accumulators[0].data = Some(5);
+        assert_eq!(Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 3).describe(&args), "a0 := a0 + 3  ; a0 = 5 -> 8");
+        assert_eq!(args.accumulators[0].data, Some(5));
+        assert_eq!(Instruction::GotoIfConstant(Comparison::Less, "loop", 0, 10).describe(&args), "if a0 < 10 then goto loop  ; a0 = 5");
+        assert_eq!(Instruction::Pop().describe(&setup_empty_runtime_args()), "pop  ; Accumulator with index 0 does not exist!");
+        assert_eq!(Instruction::GotoAccumulator(0).describe(&args), "goto a0  ; a0 = 5");
+        assert_eq!(Instruction::Goto("end").describe(&args), "goto end");
+        assert_eq!(Instruction::Nop().describe(&args), "nop");
+    }
+
+    #[test]
+    fn test_goto_if_stack_pop() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        control_flow.instruction_labels.insert("loop", 20);
+        args.stack = vec![7, 3];
+        Instruction::GotoIfStackPop(Comparison::More, "loop", 5).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(control_flow.next_instruction_index, 0);
+        assert_eq!(args.stack, vec![7]);
+        Instruction::GotoIfStackPop(Comparison::More, "loop", 5).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(control_flow.next_instruction_index, 20);
+        assert!(args.stack.is_empty());
+        assert_eq!(Instruction::GotoIfStackPop(Comparison::More, "loop", 5).run(&mut args, &mut control_flow), Err(RuntimeError::StackEmpty));
+    }
+
+    #[test]
+    fn test_move_accumulator() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.accumulators[1].data = Some(4);
+        Instruction::MoveAccumulator(0, 1).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(4));
+        assert_eq!(args.accumulators[1].data, None);
+        assert_eq!(Instruction::MoveAccumulator(0, 1).run(&mut args, &mut control_flow), Err(RuntimeError::AccumulatorUninitialized(1)));
+        Instruction::MoveAccumulator(0, 0).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(4));
+    }
+
+    #[test]
+    fn test_assert_accumulators_equal() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.accumulators[0].data = Some(3);
+        assert_eq!(Instruction::AssertAccumulatorsEqual(0, 1).run(&mut args, &mut control_flow), Err(RuntimeError::AccumulatorUninitialized(1)));
+        args.accumulators[1].data = Some(3);
+        assert!(Instruction::AssertAccumulatorsEqual(0, 1).run(&mut args, &mut control_flow).is_ok());
+        args.accumulators[1].data = Some(4);
+        let error = Instruction::AssertAccumulatorsEqual(0, 1).run(&mut args, &mut control_flow).unwrap_err();
+        assert_eq!(error.to_string(), "Assertion failed: a0 = a1 (a0 = 3, a1 = 4)!");
+    }
+
+    #[test]
+    fn test_assign_accumulator_from_indexed() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.accumulators[1].data = Some(8);
+        args.accumulators[2].data = Some(1);
+        Instruction::AssignAccumulatorFromIndexed(0, 2).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(8));
+        args.accumulators[2].data = Some(5);
+        assert_eq!(Instruction::AssignAccumulatorFromIndexed(0, 2).run(&mut args, &mut control_flow), Err(RuntimeError::AccumulatorDoesNotExist(5)));
+        args.accumulators[2].data = Some(-1);
+        assert_eq!(Instruction::AssignAccumulatorFromIndexed(0, 2).run(&mut args, &mut control_flow), Err(RuntimeError::InvalidIndex(-1)));
+    }
+
+    #[test]
+    fn test_push_if() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.accumulators[1].data = Some(4);
+        Instruction::PushIf(Comparison::More, 1, 5).run(&mut args, &mut control_flow).unwrap();
+        assert!(args.stack.is_empty());
+        Instruction::PushIf(Comparison::Less, 1, 5).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.stack, vec![4]);
+        assert_eq!(Instruction::PushIf(Comparison::Less, 0, 5).run(&mut args, &mut control_flow), Err(RuntimeError::AccumulatorUninitialized(0)));
+    }
+
+    #[test]
+    fn test_abs_diff() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.accumulators[1].data = Some(3);
+        args.accumulators[2].data = Some(-4);
+        Instruction::AbsDiff(0, 1, 2).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(7));
+        Instruction::AbsDiff(0, 2, 1).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(7));
+        args.accumulators[1].data = Some(i32::MAX);
+        assert!(matches!(Instruction::AbsDiff(0, 1, 2).run(&mut args, &mut control_flow), Err(RuntimeError::Overflow(_))));
+    }
+
+    #[test]
+    fn test_accumulator_soft_limit() {
+        let mut args = setup_empty_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        for _ in 0..6 {
+            args.add_accumulator();
+        }
+        args.accumulator_soft_limit = Some(3);
+        Instruction::AssignAccumulatorValue(3, 1).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(Instruction::AssignAccumulatorValue(5, 1).run(&mut args, &mut control_flow), Err(RuntimeError::AccumulatorAboveSoftLimit(5, 3)));
+        assert_eq!(Instruction::AssignAccumulatorValueFromAccumulator(0, 5).run(&mut args, &mut control_flow), Err(RuntimeError::AccumulatorAboveSoftLimit(5, 3)));
+        assert!(args.accumulators[5].data.is_none());
+        // instructions that access all accumulators only access the ones up to the limit
+        args.accumulators[5].data = Some(9);
+        Instruction::PushAllAccumulators().run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.stack, vec![0, 0, 0, 1]);
+        args.stack = vec![1, 2, 3, 4];
+        Instruction::PopAllAccumulators().run(&mut args, &mut control_flow).unwrap();
+        assert!(args.stack.is_empty());
+        assert_eq!(args.accumulators[3].data, Some(4));
+        assert_eq!(args.accumulators[5].data, Some(9));
+        Instruction::ResetState().run(&mut args, &mut control_flow).unwrap();
+        assert!(args.accumulators[3].data.is_none());
+        assert_eq!(args.accumulators[5].data, Some(9));
+    }
+
+    #[test]
+    fn test_print_labeled() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.captured_output = Some(Vec::new());
+        assert_eq!(Instruction::PrintLabeled("Result", 0).run(&mut args, &mut control_flow), Err(RuntimeError::AccumulatorUninitialized(0)));
+        args.accumulators[0].data = Some(42);
+        Instruction::PrintLabeled("Result", 0).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.captured_output.unwrap(), vec![String::from("Result: 42")]);
+    }
+
+    #[test]
+    fn test_transform_accumulator() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        let transforms = [(Transform::Square, 49), (Transform::Double, 14), (Transform::Half, 3)];
+        for (transform, expected) in transforms {
+            args.accumulators[0].data = Some(7);
+            Instruction::TransformAccumulator(0, transform).run(&mut args, &mut control_flow).unwrap();
+            assert_eq!(args.accumulators[0].data, Some(expected));
+        }
+        args.accumulators[0].data = Some(-7);
+        Instruction::TransformAccumulator(0, Transform::Half).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(-3));
+        args.accumulators[0].data = Some(i32::MAX);
+        assert!(matches!(Instruction::TransformAccumulator(0, Transform::Double).run(&mut args, &mut control_flow), Err(RuntimeError::Overflow(_))));
+        assert_eq!(args.accumulators[0].data, Some(i32::MAX));
+    }
+
+    #[test]
+    fn test_reset_state() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.accumulators[1].data = Some(3);
+        args.memory_cells.get_mut("b").unwrap().data = Some(4);
+        args.stack = vec![1, 2];
+        Instruction::ResetState().run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators.len(), 3);
+        assert!(args.accumulators.iter().all(|a| a.data.is_none()));
+        assert_eq!(args.memory_cells.len(), 3);
+        assert!(args.memory_cells.values().all(|m| m.data.is_none()));
+        assert!(args.stack.is_empty());
+    }
+
+    #[test]
+    fn test_goto_if_stack_top_memory_cell() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        control_flow.instruction_labels.insert("loop", 20);
+        args.memory_cells.get_mut("a").unwrap().data = Some(5);
+        assert_eq!(Instruction::GotoIfStackTopMemoryCell(Comparison::Less, "loop", "a").run(&mut args, &mut control_flow), Err(RuntimeError::StackEmpty));
+        args.stack = vec![7];
+        Instruction::GotoIfStackTopMemoryCell(Comparison::Less, "loop", "a").run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(control_flow.next_instruction_index, 0);
+        Instruction::GotoIfStackTopMemoryCell(Comparison::More, "loop", "a").run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(control_flow.next_instruction_index, 20);
+        assert_eq!(args.stack, vec![7]);
+        assert_eq!(Instruction::GotoIfStackTopMemoryCell(Comparison::More, "loop", "z").run(&mut args, &mut control_flow), Err(RuntimeError::MemoryCellDoesNotExist(String::from("z"))));
+    }
+
+    #[test]
+    fn test_push_memory_cell() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        assert_eq!(Instruction::PushMemoryCell("a").run(&mut args, &mut control_flow), Err(RuntimeError::MemoryCellUninitialized(String::from("a"))));
+        args.memory_cells.get_mut("a").unwrap().data = Some(42);
+        Instruction::PushMemoryCell("a").run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.stack_top(), Some(42));
+    }
+
+    #[test]
+    fn test_pop_memory_cell() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        assert_eq!(Instruction::PopMemoryCell("a").run(&mut args, &mut control_flow), Err(RuntimeError::StackEmpty));
+        args.stack = vec![1, 2];
+        assert_eq!(Instruction::PopMemoryCell("z").run(&mut args, &mut control_flow), Err(RuntimeError::MemoryCellDoesNotExist(String::from("z"))));
+        Instruction::PopMemoryCell("a").run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.memory_cells.get("a").unwrap().data, Some(2));
+        assert_eq!(args.stack, vec![1]);
+    }
+
+    #[test]
+    fn test_stack_len_minus() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.stack = vec![4, 5, 6];
+        Instruction::StackLenMinus(0, 1).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(2));
+        Instruction::StackLenMinus(0, 5).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(-2));
+    }
+
+    #[test]
+    fn test_clear_memory_cell_if() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.memory_cells.get_mut("a").unwrap().data = Some(0);
+        args.memory_cells.get_mut("b").unwrap().data = Some(7);
+        Instruction::ClearMemoryCellIf(Comparison::More, "a", 0, "b").run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.memory_cells.get("b").unwrap().data, Some(7));
+        Instruction::ClearMemoryCellIf(Comparison::Equal, "a", 0, "b").run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.memory_cells.get("b").unwrap().data, None);
+        assert_eq!(args.memory_cells.get("a").unwrap().data, Some(0));
+    }
+
+    #[test]
+    fn test_swap_accumulator_memory_cell() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.accumulators[0].data = Some(5);
+        args.memory_cells.get_mut("a").unwrap().data = Some(9);
+        Instruction::SwapAccumulatorMemoryCell(0, "a").run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(9));
+        assert_eq!(args.memory_cells.get("a").unwrap().data, Some(5));
+        Instruction::SwapAccumulatorMemoryCell(1, "a").run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[1].data, Some(5));
+        assert_eq!(args.memory_cells.get("a").unwrap().data, None);
+        assert_eq!(Instruction::SwapAccumulatorMemoryCell(0, "z").run(&mut args, &mut control_flow), Err(RuntimeError::MemoryCellDoesNotExist(String::from("z"))));
+    }
+
+    #[test]
+    fn test_gcd() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        let cases = [(12, 8, 4), (8, 12, 4), (-12, 8, 4), (7, 0, 7), (0, 0, 0)];
+        for (b, c, expected) in cases {
+            args.accumulators[1].data = Some(b);
+            args.accumulators[2].data = Some(c);
+            Instruction::Gcd(0, 1, 2).run(&mut args, &mut control_flow).unwrap();
+            assert_eq!(args.accumulators[0].data, Some(expected));
+        }
+        for (b, c) in [(i32::MIN, 0), (0, i32::MIN), (i32::MIN, i32::MIN)] {
+            args.accumulators[1].data = Some(b);
+            args.accumulators[2].data = Some(c);
+            assert_eq!(Instruction::Gcd(0, 1, 2).run(&mut args, &mut control_flow), Err(RuntimeError::Overflow(format!("gcd({}, {})", b, c))));
+        }
+    }
+
+    #[test]
+    fn test_pop_count() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        let cases = [(7, 3), (0, 0), (8, 1), (-1, 32)];
+        for (b, expected) in cases {
+            args.accumulators[1].data = Some(b);
+            Instruction::PopCount(0, 1).run(&mut args, &mut control_flow).unwrap();
+            assert_eq!(args.accumulators[0].data, Some(expected));
+        }
+        args.accumulators[2].data = None;
+        assert_eq!(Instruction::PopCount(0, 2).run(&mut args, &mut control_flow), Err(RuntimeError::AccumulatorUninitialized(2)));
+    }
+
+    #[test]
+    fn test_min_max_of_cells() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        for (label, value) in [("x0", 4), ("x1", -2), ("x2", 9)] {
+            args.add_storage_cell(label);
+            args.memory_cells.get_mut(label).unwrap().data = Some(value);
+        }
+        Instruction::MinOfCells(0, "x", 3).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(-2));
+        Instruction::MaxOfCells(0, "x", 3).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(9));
+        Instruction::MinOfCells(0, "x", 1).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(4));
+        assert_eq!(Instruction::MinOfCells(0, "x", 4).run(&mut args, &mut control_flow), Err(RuntimeError::MemoryCellDoesNotExist(String::from("x3"))));
+        args.memory_cells.get_mut("x1").unwrap().data = None;
+        assert_eq!(Instruction::MaxOfCells(0, "x", 3).run(&mut args, &mut control_flow), Err(RuntimeError::MemoryCellUninitialized(String::from("x1"))));
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.accumulators[0].data = Some(0b1011);
+        Instruction::RotateLeft(0, 4).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(0b1011_0000));
+        Instruction::RotateRight(0, 6).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(0xC000_0002_u32 as i32));
+        Instruction::RotateLeft(0, 2).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(0b1011));
+        args.accumulators[0].data = Some(i32::MIN);
+        Instruction::RotateLeft(0, 1).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(1));
+        args.accumulators[0].data = None;
+        assert_eq!(Instruction::RotateRight(0, 1).run(&mut args, &mut control_flow), Err(RuntimeError::AccumulatorUninitialized(0)));
+    }
+
+    #[test]
+    fn test_sum_cells() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        for (label, value) in [("x0", 1), ("x1", 2), ("x2", 3)] {
+            args.add_storage_cell(label);
+            args.memory_cells.get_mut(label).unwrap().data = Some(value);
+        }
+        Instruction::SumCells(0, "x", 3).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(6));
+        Instruction::SumCells(0, "x", 0).run(&mut args, &mut control_flow).unwrap();
+        assert_eq!(args.accumulators[0].data, Some(0));
+        assert_eq!(Instruction::SumCells(0, "x", 4).run(&mut args, &mut control_flow), Err(RuntimeError::MemoryCellDoesNotExist(String::from("x3"))));
+        args.memory_cells.get_mut("x2").unwrap().data = Some(i32::MAX);
+        assert_eq!(Instruction::SumCells(0, "x", 3).run(&mut args, &mut control_flow), Err(RuntimeError::Overflow(format!("3 + {}", i32::MAX))));
+        args.memory_cells.get_mut("x1").unwrap().data = None;
+        assert_eq!(Instruction::SumCells(0, "x", 3).run(&mut args, &mut control_flow), Err(RuntimeError::MemoryCellUninitialized(String::from("x1"))));
+    }
+
+    #[test]
+    fn test_calc_errors() {
+        let mut args = setup_runtime_args();
+        let mut control_flow = ControlFlow::new();
+        args.accumulators[0].data = Some(i32::MIN);
+        args.accumulators[1].data = Some(-1);
+        assert_eq!(Instruction::CalcAccumulatorWithConstant(Operation::Division, 0, 0).run(&mut args, &mut control_flow), Err(RuntimeError::DivisionByZero));
+        assert_eq!(Instruction::CalcAccumulatorWithAccumulator(Operation::Division, 0, 1).run(&mut args, &mut control_flow), Err(RuntimeError::Overflow(format!("{} / -1", i32::MIN))));
+        assert_eq!(Instruction::CalcAccumulatorWithConstant(Operation::Minus, 0, 1).run(&mut args, &mut control_flow), Err(RuntimeError::Overflow(format!("{} - 1", i32::MIN))));
+        assert_eq!(args.accumulators[0].data, Some(i32::MIN));
+    }
+
     /// Sets up runtime args in a conistent way because the default implementation for memory cells and accumulators is configgurable.
     fn setup_runtime_args() -> RuntimeArgs<'static> {
         let mut args = RuntimeArgs::new();
@@ -1,4 +1,4 @@
-use crate::{instructions::Instruction, runtime::{Runner, RuntimeArgs}, base::{Operation, Comparison}};
+use crate::{instructions::Instruction, runtime::Runner};
 
 /// Contains all required data types used to run programs
 mod base;
@@ -6,13 +6,16 @@ mod base;
 mod runtime;
 /// Supported instructions
 mod instructions;
+/// Floating point variants of the data types used to run programs
+#[cfg(feature = "float")]
+mod float;
 
 /// Used to set the maximum number of accumulators.
 ///
 /// Should be at least 1.
 const ACCUMULATORS: i32 = 4;
 /// Used to set the available memory cells.
-const MEMORY_CELL_LABELS: &'static [&'static str] = &["a", "b", "c", "d", "e", "f"];
+const MEMORY_CELL_LABELS: &[&str] = &["a", "b", "c", "d", "e", "f"];
 
 fn main() {
     println!("Hello, world!");
@@ -32,6 +35,7 @@ fn main() {
         Instruction::PrintStack(),
     ];
     let mut runner = Runner::new(instructions);
-    runner.run().unwrap();
-    
+    if let Err(e) = runner.run() {
+        println!("Unable to continue execution, an irrecoverable error occured: {}", e);
+    }
 }
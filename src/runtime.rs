@@ -1,46 +1,421 @@
-use std::collections::HashMap;
+use std::{collections::{hash_map::DefaultHasher, HashMap, HashSet, VecDeque}, hash::{Hash, Hasher}, io::Write, ops::Range, time::{Duration, Instant}};
 
-use crate::{instructions::Instruction, base::{Accumulator, MemoryCell}, ACCUMULATORS, MEMORY_CELL_LABELS};
+use crate::{instructions::{Instruction, InstructionKind}, base::{Accumulator, MemoryCell, Operation, RuntimeError}, ACCUMULATORS, MEMORY_CELL_LABELS};
+
+/// Number of instructions that are executed between two checks of the clock in [run_with_timeout](struct.Runner.html#method.run_with_timeout).
+#[allow(dead_code)]
+const TIMEOUT_CHECK_INTERVAL: usize = 1000;
+
+/// Function that can replace the index of the next instruction, see [set_fetch_hook](struct.Runner.html#method.set_fetch_hook).
+pub type FetchHook<'a> = Box<dyn FnMut(usize) -> usize + 'a>;
 
 //TODO make fields private and add access functions, move into separate module
 pub struct Runner<'a> {
     runtime_args: RuntimeArgs<'a>,
+    /// The runtime args the runner was created with, used to [reset](#method.reset) the runner.
+    initial_runtime_args: RuntimeArgs<'a>,
     instructions: Vec<Instruction<'a>>,
     control_flow: ControlFlow<'a>,
+    /// Instruction indices the labels of goto instructions have been resolved to by [link](#method.link).
+    /// 
+    /// Empty when the program is not linked.
+    linked_indices: Vec<Option<usize>>,
+    /// How often the instruction at each index has been executed.
+    execution_counts: Vec<u64>,
+    /// The largest size the stack had during execution.
+    max_stack_depth: usize,
+    /// States and next instruction indices before the last executed instructions, the newest entry is at the back.
+    history: VecDeque<(RuntimeArgs<'a>, usize)>,
+    /// The number of states that are kept in **history**, history is disabled when 0.
+    history_depth: usize,
+    /// When set, each executed instruction is recorded in **trace**.
+    tracing: bool,
+    trace: Trace,
+    /// Smallest and largest value each memory cell held during execution, None when ranges are not recorded.
+    value_ranges: Option<HashMap<String, (i32, i32)>>,
+    /// Total time spent executing and number of executed instructions of each kind, None when latencies are not recorded.
+    latencies: Option<HashMap<InstructionKind, (Duration, u64)>>,
+    /// Called with the index of the next instruction before it is executed, the returned index is executed instead.
+    /// 
+    /// Not copied when the runner is cloned.
+    fetch_hook: Option<FetchHook<'a>>,
 }
 
-impl<'a> Runner<'a> {
-    pub fn new(instructions: Vec<Instruction<'a>>) -> Self {
+impl Clone for Runner<'_> {
+    /// Copies the runner without its fetch hook, so that the copy does not share mutable state with the original,
+    /// see [set_fetch_hook](#method.set_fetch_hook).
+    fn clone(&self) -> Self {
         Self {
-            runtime_args: RuntimeArgs::new(),
-            instructions,
-            control_flow: ControlFlow::new(),
+            runtime_args: self.runtime_args.clone(),
+            initial_runtime_args: self.initial_runtime_args.clone(),
+            instructions: self.instructions.clone(),
+            control_flow: self.control_flow.clone(),
+            linked_indices: self.linked_indices.clone(),
+            execution_counts: self.execution_counts.clone(),
+            max_stack_depth: self.max_stack_depth,
+            history: self.history.clone(),
+            history_depth: self.history_depth,
+            tracing: self.tracing,
+            trace: self.trace.clone(),
+            value_ranges: self.value_ranges.clone(),
+            latencies: self.latencies.clone(),
+            fetch_hook: None,
         }
     }
+}
+
+#[allow(dead_code)]
+impl<'a> Runner<'a> {
+    pub fn new(instructions: Vec<Instruction<'a>>) -> Self {
+        Self::new_custom(instructions, RuntimeArgs::new())
+    }
 
     /// Creates a new runner that can be initialized with different runtime args.
     pub fn new_custom(instructions: Vec<Instruction<'a>>, runtime_args: RuntimeArgs<'a>) -> Self {
         Self {
+            initial_runtime_args: runtime_args.clone(),
             runtime_args,
             instructions,
             control_flow: ControlFlow::new(),
+            linked_indices: Vec::new(),
+            execution_counts: Vec::new(),
+            max_stack_depth: 0,
+            history: VecDeque::new(),
+            history_depth: 0,
+            tracing: false,
+            trace: Vec::new(),
+            value_ranges: None,
+            latencies: None,
+            fetch_hook: None,
+        }
+    }
+
+    /// Returns the program together with its current state, so that it can be continued later with [from_checkpoint](#method.from_checkpoint).
+    /// 
+    /// With the serde feature the checkpoint can be serialized.
+    pub fn checkpoint(&self) -> Checkpoint<'a> {
+        Checkpoint {
+            instructions: self.instructions.clone(),
+            labels: self.control_flow.instruction_labels.clone(),
+            runtime_args: self.runtime_args.clone(),
+            next_instruction_index: self.control_flow.next_instruction_index,
+            call_stack: self.control_flow.call_stack.clone(),
+        }
+    }
+
+    /// Creates a new runner that continues the program from **checkpoint**, see [checkpoint](#method.checkpoint).
+    /// 
+    /// [reset](#method.reset) restores the state of the checkpoint, but starts at the first instruction.
+    pub fn from_checkpoint(checkpoint: Checkpoint<'a>) -> Self {
+        let mut runner = Runner::new_custom(checkpoint.instructions, checkpoint.runtime_args);
+        runner.control_flow.instruction_labels = checkpoint.labels;
+        runner.control_flow.next_instruction_index = checkpoint.next_instruction_index;
+        runner.control_flow.call_stack = checkpoint.call_stack;
+        runner
+    }
+
+    /// Returns the instructions and labels of the program as json, the runtime args are not included.
+    /// 
+    /// See [program_from_json](#method.program_from_json)
+    #[cfg(feature = "serde")]
+    pub fn program_to_json(&self) -> String {
+        serde_json::json!({
+            "instructions": self.instructions,
+            "labels": self.control_flow.instruction_labels,
+        }).to_string()
+    }
+
+    /// Creates a new runner with empty runtime args from a program that has been exported with [program_to_json](#method.program_to_json).
+    /// 
+    /// The labels borrow from **json**, so labels that contain escaped characters can not be read.
+    /// 
+    /// Errors when **json** does not contain a valid program or a label points outside of the program.
+    #[cfg(feature = "serde")]
+    pub fn program_from_json(json: &'a str) -> Result<Self, String> {
+        let program: Program<'a> = serde_json::from_str(json).map_err(|e| format!("Unable to read program: {}", e))?;
+        let mut runner = Runner::new(program.instructions);
+        for (label, index) in program.labels {
+            runner.add_label(label, index)?;
+        }
+        Ok(runner)
+    }
+
+    pub fn run(&mut self) -> Result<(), RuntimeError> {
+        while !self.finished() {
+            self.step()?;
+        }
+        Ok(())
+    }
+
+    /// Runs the program and writes a line for each executed instruction to **w**,
+    /// the line contains the instruction and the values it changed, for example **a0 := a0 + 3  ; a0: 5 -> 8**.
+    /// 
+    /// Errors that occur while writing to **w** are ignored.
+    pub fn run_logged(&mut self, mut w: impl Write) -> Result<(), RuntimeError> {
+        while !self.finished() {
+            let state_before = self.runtime_args.clone();
+            let (index, result) = self.step_with_index();
+            let Some(index) = index else {
+                break;
+            };
+            let changes = state_before.diff_report(&self.runtime_args);
+            let changes: Vec<&str> = changes.lines().collect();
+            let _ = if changes.is_empty() {
+                writeln!(w, "{}", self.instructions[index])
+            } else {
+                writeln!(w, "{}  ; {}", self.instructions[index], changes.join(", "))
+            };
+            result?;
         }
+        Ok(())
     }
 
-    pub fn run(&mut self) -> Result<(), String> {
-        while self.control_flow.next_instruction_index < self.instructions.len() {
-            let current_instruction = self.control_flow.next_instruction_index;
-            self.control_flow.next_instruction_index += 1;
-            if let Err(e) = self.instructions[current_instruction].run(&mut self.runtime_args, &mut self.control_flow) {
-                println!("Unable to continue execution, an irrecoverable error occured: {}", e);
-                return Err(format!("Execution terminated: {}", e));
+    /// Runs the program and returns the lines that have been printed by the print instructions
+    /// instead of printing them into the console.
+    pub fn run_capturing(&mut self) -> Result<Vec<String>, RuntimeError> {
+        self.runtime_args.captured_output = Some(Vec::new());
+        let result = self.run();
+        let captured_output = self.runtime_args.captured_output.take().unwrap_or_default();
+        result.map(|_| captured_output)
+    }
+
+    /// Runs the program and returns the printed lines, the final state and the number of executed instructions together.
+    /// 
+    /// Like in [run_capturing](#method.run_capturing) the printed lines are not printed into the console.
+    pub fn run_full(&mut self) -> Result<RunOutcome<'a>, RuntimeError> {
+        self.runtime_args.captured_output = Some(Vec::new());
+        let mut executed_instructions = 0;
+        let mut result = Ok(());
+        while !self.finished() {
+            match self.step() {
+                Ok(Some(_)) => executed_instructions += 1,
+                Ok(None) => (),
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        let output = self.runtime_args.captured_output.take().unwrap_or_default();
+        result.map(|_| RunOutcome {
+            output,
+            runtime_args: self.runtime_args.clone(),
+            executed_instructions,
+        })
+    }
+
+    /// Runs the program until the instruction at **label** is about to be executed or until the program is finished.
+    /// 
+    /// At least one instruction is executed, so that calling this repeatedly runs from one occurrence of the label to the next.
+    /// 
+    /// Errors when the label does not exist.
+    pub fn run_to_label(&mut self, label: &str) -> Result<(), RuntimeError> {
+        let target = *self.control_flow.instruction_labels.get(label).ok_or_else(|| RuntimeError::LabelMissing(label.to_string()))?;
+        while !self.finished() {
+            self.step()?;
+            if self.control_flow.next_instruction_index == target {
+                break;
             }
         }
         Ok(())
     }
 
+    /// Runs the program once for each input and returns the final state of each run.
+    /// 
+    /// Before each run the runner is [reset](#method.reset) and the accumulators are set to the values in the input.
+    /// An input consists of pairs of accumulator index and value.
+    pub fn run_batch(&mut self, inputs: &[Vec<(usize, i32)>]) -> Vec<Result<RuntimeArgs<'a>, RuntimeError>> {
+        let mut results = Vec::new();
+        for input in inputs {
+            self.reset();
+            let result = input.iter()
+                .try_for_each(|(index, value)| self.runtime_args.set_accumulator(*index, *value))
+                .and_then(|_| self.run())
+                .map(|_| self.runtime_args.clone());
+            results.push(result);
+        }
+        results
+    }
+
+    /// Runs the program once for each value in **range**, the value is stored in the accumulator with index **a_idx** before each run.
+    /// 
+    /// Returns each value together with the state the program ended in. When a run fails, the state at the time of the error is returned.
+    pub fn explore_bounded(&mut self, a_idx: usize, range: Range<i32>) -> Vec<(i32, RuntimeArgs<'a>)> {
+        let mut results = Vec::new();
+        for value in range {
+            self.reset();
+            let _ = self.runtime_args.set_accumulator(a_idx, value).and_then(|_| self.run());
+            results.push((value, self.runtime_args.clone()));
+        }
+        results
+    }
+
+    /// Runs this program and **other** with each input, see [run_batch](#method.run_batch),
+    /// and returns true when both programs end in the same state for every input.
+    /// 
+    /// The accumulators, memory cells and the stack are compared. When a run fails, the other program has to fail with the same error.
+    pub fn behaves_like(&mut self, other: &mut Runner<'a>, inputs: &[Vec<(usize, i32)>]) -> bool {
+        self.run_batch(inputs).iter().zip(other.run_batch(inputs).iter()).all(|results| match results {
+            (Ok(own_state), Ok(other_state)) => own_state.diff_report(other_state).is_empty(),
+            (Err(own_error), Err(other_error)) => own_error == other_error,
+            _ => false,
+        })
+    }
+
+    /// Resets the runner to the state it had when it was created.
+    /// 
+    /// The instructions and labels are kept.
+    pub fn reset(&mut self) {
+        self.runtime_args = self.initial_runtime_args.clone();
+        self.control_flow.next_instruction_index = 0;
+        self.control_flow.call_stack.clear();
+        self.execution_counts.clear();
+        self.max_stack_depth = 0;
+        self.history.clear();
+        self.trace.clear();
+        if let Some(value_ranges) = &mut self.value_ranges {
+            value_ranges.clear();
+        }
+        if let Some(latencies) = &mut self.latencies {
+            latencies.clear();
+        }
+    }
+
+    /// Runs the program until it finishes or until **max_instructions** instructions have been executed.
+    /// 
+    /// Ok(true) when the program finished, Ok(false) when the instruction limit was reached first.
+    pub fn run_with_limit(&mut self, max_instructions: usize) -> Result<bool, RuntimeError> {
+        for _ in 0..max_instructions {
+            if self.finished() {
+                return Ok(true);
+            }
+            self.step()?;
+        }
+        Ok(self.finished())
+    }
+
+    /// Runs the program until it finishes, errors with [Timeout](../base/enum.RuntimeError.html#variant.Timeout) when it takes longer than **timeout**.
+    /// 
+    /// The clock is only checked every [TIMEOUT_CHECK_INTERVAL] instructions, so the program may run slightly longer than **timeout**.
+    pub fn run_with_timeout(&mut self, timeout: Duration) -> Result<(), RuntimeError> {
+        let start = Instant::now();
+        let mut steps: usize = 0;
+        while !self.finished() {
+            if steps.is_multiple_of(TIMEOUT_CHECK_INTERVAL) && start.elapsed() > timeout {
+                return Err(RuntimeError::Timeout(timeout));
+            }
+            self.step()?;
+            steps = steps.wrapping_add(1);
+        }
+        Ok(())
+    }
+
+    /// Runs the whole program repeatedly, without resetting the state in between, until a pass does not change the state anymore.
+    /// 
+    /// Ok(Some(passes)) contains the number of passes that changed the state, Ok(None) is returned when the state
+    /// still changes after **max_passes** passes. States are compared with [state_hash](struct.RuntimeArgs.html#method.state_hash).
+    pub fn run_until_stable(&mut self, max_passes: usize) -> Result<Option<usize>, RuntimeError> {
+        let mut hash = self.runtime_args.state_hash();
+        for passes in 0..max_passes {
+            self.control_flow.next_instruction_index = 0;
+            self.control_flow.call_stack.clear();
+            self.run()?;
+            let new_hash = self.runtime_args.state_hash();
+            if new_hash == hash {
+                return Ok(Some(passes));
+            }
+            hash = new_hash;
+        }
+        Ok(None)
+    }
+
+    /// Checks if the program halts within **max_instructions** executed instructions.
+    /// 
+    /// Note: A program that stops because of an error is considered to be halted.
+    pub fn terminates_within(&mut self, max_instructions: usize) -> bool {
+        !matches!(self.run_with_limit(max_instructions), Ok(false))
+    }
+
+    /// Returns the index of the instruction that is executed next.
+    pub fn next_instruction_index(&self) -> usize {
+        self.control_flow.next_instruction_index
+    }
+
+    /// Returns true when there are no more instructions left to execute.
+    pub fn finished(&self) -> bool {
+        self.control_flow.next_instruction_index >= self.instructions.len()
+    }
+
+    /// Executes the next instruction and returns its index.
+    /// 
+    /// Does nothing and returns None when the program is finished or when the [fetch hook](#method.set_fetch_hook) ends the program.
+    pub fn step(&mut self) -> Result<Option<usize>, RuntimeError> {
+        let (index, result) = self.step_with_index();
+        result.map(|_| index)
+    }
+
+    /// Like [step](#method.step), but also returns the index of the executed instruction when it failed,
+    /// so that the error can be located, see [to_json](../base/enum.RuntimeError.html#method.to_json).
+    pub fn step_with_index(&mut self) -> (Option<usize>, Result<(), RuntimeError>) {
+        if self.finished() {
+            return (None, Ok(()));
+        }
+        if let Some(fetch_hook) = &mut self.fetch_hook {
+            self.control_flow.next_instruction_index = fetch_hook(self.control_flow.next_instruction_index);
+            if self.finished() {
+                return (None, Ok(()));
+            }
+        }
+        if self.history_depth > 0 {
+            if self.history.len() >= self.history_depth {
+                self.history.pop_front();
+            }
+            self.history.push_back((self.runtime_args.clone(), self.control_flow.next_instruction_index));
+        }
+        let current_instruction = self.control_flow.next_instruction_index;
+        self.control_flow.next_instruction_index += 1;
+        if self.execution_counts.len() <= current_instruction {
+            self.execution_counts.resize(current_instruction + 1, 0);
+        }
+        self.execution_counts[current_instruction] += 1;
+        let linked_index = self.linked_indices.get(current_instruction).copied().flatten();
+        let state_before = if self.tracing { Some(self.runtime_args.clone()) } else { None };
+        let start = self.latencies.as_ref().map(|_| Instant::now());
+        let result = self.instructions[current_instruction].run_linked(&mut self.runtime_args, &mut self.control_flow, linked_index);
+        if let (Some(latencies), Some(start)) = (&mut self.latencies, start) {
+            let latency = latencies.entry(self.instructions[current_instruction].name()).or_insert((Duration::ZERO, 0));
+            latency.0 += start.elapsed();
+            latency.1 += 1;
+        }
+        if let Some(state_before) = state_before {
+            self.trace.push(TraceStep {
+                instruction_index: current_instruction,
+                changes: state_before.diff_report(&self.runtime_args),
+            });
+        }
+        self.max_stack_depth = self.max_stack_depth.max(self.runtime_args.stack.len());
+        if let Some(value_ranges) = &mut self.value_ranges {
+            for (label, memory_cell) in &self.runtime_args.memory_cells {
+                if let Some(value) = memory_cell.data {
+                    let range = value_ranges.entry(label.to_string()).or_insert((value, value));
+                    *range = (range.0.min(value), range.1.max(value));
+                }
+            }
+        }
+        (Some(current_instruction), result)
+    }
+
+    /// Sets a function that is called with the index of the next instruction each time before an instruction is executed,
+    /// the instruction at the returned index is executed instead. The program ends when the returned index is out of bounds.
+    /// 
+    /// The hook is not copied when the runner is cloned, clones run without a hook.
+    pub fn set_fetch_hook(&mut self, fetch_hook: FetchHook<'a>) {
+        self.fetch_hook = Some(fetch_hook);
+    }
+
     /// Adds an instruction to the end of the instruction vector with a label mapping.
     pub fn add_instruction_with_label(&mut self, instruction: Instruction<'a>, label: &'a str) {
+        self.linked_indices.clear();
         self.instructions.push(instruction);
         self.control_flow.instruction_labels.insert(label, self.instructions.len()-1);
     }
@@ -54,19 +429,743 @@ impl<'a> Runner<'a> {
         if self.instructions.len() <= instruction_index {
             Err(format!("Unable to add label {}, index {} is out of bounds!", label, instruction_index))
         } else {
+            self.linked_indices.clear();
             self.control_flow.instruction_labels.insert(label, instruction_index);
             Ok(())
         }
     }
 
+    /// Adds the instructions of another program to the end of this program.
+    /// 
+    /// **labels** are the labels of the other program, each written with **prefix** in front of the label that the other program jumps to,
+    /// for example **lib_loop** for the jump target **loop** with the prefix **lib_**.
+    /// Their indices are moved behind the existing instructions and the jumps of the other program are renamed to them.
+    /// Jumps to labels that are not in **labels** are not renamed, so that the other program can jump into this program.
+    /// Memory cells that are used by the other program and don't exist yet are added.
+    /// 
+    /// Errors when a label does not start with **prefix**, when a label already exists in this program
+    /// or when a label points outside of the other program, nothing is added in that case.
+    /// 
+    /// Note: Memory cells that are addressed by a base label, see [memory_cell_labels](../instructions/enum.Instruction.html#method.memory_cell_labels),
+    /// are not added, they have to exist already.
+    pub fn append_program(&mut self, other: Vec<Instruction<'a>>, labels: &[(&'a str, usize)], prefix: &str) -> Result<(), String> {
+        let mut prefixed_labels: HashMap<&'a str, &'a str> = HashMap::new();
+        for (label, index) in labels {
+            let unprefixed_label = label.strip_prefix(prefix)
+                .ok_or_else(|| format!("Unable to append program, label {} does not start with {}!", label, prefix))?;
+            if self.control_flow.instruction_labels.contains_key(label) {
+                return Err(format!("Unable to append program, label {} already exists!", label));
+            }
+            if *index >= other.len() {
+                return Err(format!("Unable to append program, index {} of label {} is out of bounds!", index, label));
+            }
+            prefixed_labels.insert(unprefixed_label, label);
+        }
+        let offset = self.instructions.len();
+        for (label, index) in labels {
+            self.control_flow.instruction_labels.insert(label, index + offset);
+        }
+        for label in other.iter().flat_map(|instruction| instruction.memory_cell_labels()) {
+            if !self.runtime_args.memory_cells.contains_key(label) {
+                self.runtime_args.add_storage_cell(label);
+                self.initial_runtime_args.add_storage_cell(label);
+            }
+        }
+        self.instructions.extend(other.iter().map(|instruction| instruction.map_labels(|label| prefixed_labels.get(label).copied().unwrap_or(label))));
+        self.linked_indices.clear();
+        Ok(())
+    }
+
+    /// Inserts **instruction** at **index**, all labels that point to **index** or to an instruction after it
+    /// are moved by one, so that they still point to the same instruction.
+    /// 
+    /// Errors when **index** is larger than the number of instructions.
+    /// 
+    /// Note: This is meant to be used to edit a program before it is run.
+    pub fn insert_instruction(&mut self, index: usize, instruction: Instruction<'a>) -> Result<(), String> {
+        if index > self.instructions.len() {
+            return Err(format!("Unable to insert instruction, index {} is out of bounds!", index));
+        }
+        self.instructions.insert(index, instruction);
+        for target in self.control_flow.instruction_labels.values_mut() {
+            if *target >= index {
+                *target += 1;
+            }
+        }
+        if index < self.execution_counts.len() {
+            self.execution_counts.insert(index, 0);
+        }
+        self.linked_indices.clear();
+        Ok(())
+    }
+
+    /// Removes the instruction at **index** and returns it, all labels that point to an instruction after it
+    /// are moved by one, so that they still point to the same instruction.
+    /// 
+    /// Errors when **index** is out of bounds or when a label points to the instruction.
+    /// 
+    /// Note: This is meant to be used to edit a program before it is run.
+    pub fn remove_instruction(&mut self, index: usize) -> Result<Instruction<'a>, String> {
+        if index >= self.instructions.len() {
+            return Err(format!("Unable to remove instruction, index {} is out of bounds!", index));
+        }
+        if let Some((label, _)) = self.control_flow.instruction_labels.iter().find(|(_, target)| **target == index) {
+            return Err(format!("Unable to remove instruction at index {}, label {} points to it!", index, label));
+        }
+        for target in self.control_flow.instruction_labels.values_mut() {
+            if *target > index {
+                *target -= 1;
+            }
+        }
+        if index < self.execution_counts.len() {
+            self.execution_counts.remove(index);
+        }
+        self.linked_indices.clear();
+        Ok(self.instructions.remove(index))
+    }
+
+    /// Sets the index of the instruction that is executed next, can be used to start the program at a different instruction.
+    /// 
+    /// Errors when **instruction_index** is out of bounds.
+    pub fn set_start_index(&mut self, instruction_index: usize) -> Result<(), String> {
+        if self.instructions.len() <= instruction_index {
+            Err(format!("Unable to set start index, index {} is out of bounds!", instruction_index))
+        } else {
+            self.control_flow.next_instruction_index = instruction_index;
+            Ok(())
+        }
+    }
+
+    /// Returns the instructions from **label** up to and including the next [Return](../instructions/enum.Instruction.html#variant.Return) instruction,
+    /// so that they can be added to another program.
+    /// 
+    /// Errors when the label does not exist or when no return instruction follows it.
+    pub fn extract_subroutine(&self, label: &str) -> Result<Vec<Instruction<'a>>, String> {
+        let start = *self.control_flow.instruction_labels.get(label)
+            .ok_or_else(|| format!("Unable to extract subroutine: label {} does not exist!", label))?;
+        let end = self.instructions[start..].iter()
+            .position(|instruction| matches!(instruction, Instruction::Return()))
+            .ok_or_else(|| format!("Unable to extract subroutine: no return found after label {}!", label))?;
+        Ok(self.instructions[start..=start + end].to_vec())
+    }
+
+    /// Keeps the state from before the last **depth** executed instructions, so that they can be undone with [step_back](#method.step_back).
+    /// 
+    /// A depth of 0 disables the history.
+    pub fn enable_history(&mut self, depth: usize) {
+        self.history_depth = depth;
+        while self.history.len() > depth {
+            self.history.pop_front();
+        }
+    }
+
+    /// Restores the state and instruction index from before the last executed instruction.
+    /// 
+    /// Errors when no history is left.
+    /// 
+    /// Note: The execution statistics (e.g. [coverage](#method.coverage)) are not restored.
+    pub fn step_back(&mut self) -> Result<(), String> {
+        if let Some((runtime_args, instruction_index)) = self.history.pop_back() {
+            self.runtime_args = runtime_args;
+            self.control_flow.next_instruction_index = instruction_index;
+            Ok(())
+        } else {
+            Err(String::from("Unable to step back: no history left!"))
+        }
+    }
+
+    /// Resolves the labels of all goto instructions to their instruction index,
+    /// so that they don't have to be looked up each time the jump is taken.
+    /// 
+    /// Labels that don't exist are left unresolved and will still error when the jump is taken.
+    /// 
+    /// Note: Adding instructions or labels afterwards unlinks the program.
+    pub fn link(&mut self) {
+        self.linked_indices = self.instructions.iter()
+            .map(|instruction| instruction.goto_label().and_then(|label| self.control_flow.label_index(label)))
+            .collect();
+    }
+
+    /// Returns all labels together with the index of the instruction they point to,
+    /// sorted by index and then by label.
+    pub fn labels_sorted(&self) -> Vec<(&'a str, usize)> {
+        let mut labels: Vec<(&'a str, usize)> = self.control_flow.instruction_labels.iter()
+            .map(|(label, index)| (*label, *index))
+            .collect();
+        labels.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(b.0)));
+        labels
+    }
+
+    /// Starts recording each executed instruction together with the changes it made to the state,
+    /// see [trace](#method.trace).
+    pub fn enable_trace(&mut self) {
+        self.tracing = true;
+    }
+
+    /// Returns the instructions that have been executed since tracing was enabled.
+    pub fn trace(&self) -> &Trace {
+        &self.trace
+    }
+
+    /// Runs the program from the start on a copy of this runner and checks that each executed step matches **trace**,
+    /// for example a trace that was recorded with [trace](#method.trace) before the interpreter was changed.
+    /// 
+    /// At most one step more than **trace** contains is executed, errors that occur are part of the compared trace.
+    /// Like every clone, the copy runs without the [fetch hook](#method.set_fetch_hook).
+    /// Err contains the index of the first step that does not match, see [diff_traces](fn.diff_traces.html).
+    pub fn verify_trace(&self, trace: &Trace) -> Result<(), usize> {
+        let mut runner = self.clone();
+        runner.reset();
+        runner.tracing = true;
+        let _ = runner.run_with_limit(trace.len() + 1);
+        match diff_traces(trace, &runner.trace) {
+            Some(step) => Err(step),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the recorded [trace](#method.trace) as markdown table with a row for each executed instruction.
+    pub fn trace_markdown(&self) -> String {
+        let mut markdown = String::from("| Step | Instruction | Changes |\n|------|-------------|---------|\n");
+        for (step, trace_step) in self.trace.iter().enumerate() {
+            let changes: Vec<&str> = trace_step.changes.lines().collect();
+            markdown.push_str(&format!("| {} | {} | {} |\n", step + 1, self.instructions[trace_step.instruction_index], changes.join(", ")));
+        }
+        markdown
+    }
+
+    /// Starts recording the smallest and largest value of each memory cell, see [value_ranges](#method.value_ranges).
+    pub fn enable_value_ranges(&mut self) {
+        self.value_ranges.get_or_insert_with(HashMap::new);
+    }
+
+    /// Returns the smallest and largest value each memory cell held after an executed instruction, since recording was enabled.
+    /// 
+    /// Memory cells that never contained a value are not included.
+    pub fn value_ranges(&self) -> HashMap<String, (i32, i32)> {
+        self.value_ranges.clone().unwrap_or_default()
+    }
+
+    /// Starts measuring how long the executed instructions take, see [latency_report](#method.latency_report).
+    pub fn enable_latency_tracking(&mut self) {
+        self.latencies.get_or_insert_with(HashMap::new);
+    }
+
+    /// Returns the total time, the average time and the number of executions for each kind of instruction
+    /// that has been executed since measuring was enabled, the kind with the largest total time comes first.
+    pub fn latency_report(&self) -> Vec<(InstructionKind, Duration, Duration, u64)> {
+        let mut report: Vec<(InstructionKind, Duration, Duration, u64)> = self.latencies.iter()
+            .flatten()
+            .map(|(kind, (total, count))| (*kind, *total, Duration::from_nanos((total.as_nanos() / (*count).max(1) as u128) as u64), *count))
+            .collect();
+        report.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        report
+    }
+
+    /// Returns how many times the instruction at each index has been executed.
+    pub fn coverage(&self) -> Vec<u64> {
+        let mut coverage = self.execution_counts.clone();
+        coverage.resize(self.instructions.len(), 0);
+        coverage
+    }
+
+    /// Returns the program with one instruction per line, each line starts with the index of the instruction
+    /// and how many times it has been executed, see [coverage](#method.coverage).
+    pub fn annotated_listing(&self) -> String {
+        let mut listing = String::new();
+        for (index, (instruction, count)) in self.instructions.iter().zip(self.coverage()).enumerate() {
+            listing.push_str(&format!("{:>3} | {:>6}x | {}\n", index, count, instruction));
+        }
+        listing
+    }
+
+    /// Returns how many instructions use each operation, instructions without an operation are not counted.
+    /// 
+    /// See [operation](../instructions/enum.Instruction.html#method.operation)
+    pub fn operation_histogram(&self) -> HashMap<Operation, usize> {
+        let mut histogram = HashMap::new();
+        for operation in self.instructions.iter().filter_map(|instruction| instruction.operation()) {
+            *histogram.entry(operation.clone()).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    /// Returns the labels of all memory cells that are read or written by an instruction, sorted alphabetically.
+    /// 
+    /// Note: Memory cells that are only accessed by [SpillStack](../instructions/enum.Instruction.html#variant.SpillStack) are not included,
+    /// because their labels depend on the stack size.
+    pub fn referenced_cells(&self) -> Vec<String> {
+        let mut cells: Vec<String> = self.instructions.iter()
+            .flat_map(|instruction| {
+                let effects = instruction.effects();
+                effects.read_memory_cells.into_iter().chain(effects.written_memory_cells)
+            })
+            .collect();
+        cells.sort();
+        cells.dedup();
+        cells
+    }
+
+    /// Returns the labels of all memory cells that are not read or written by any instruction, sorted alphabetically.
+    pub fn unused_cells(&self) -> Vec<String> {
+        let mut used = HashSet::new();
+        for instruction in &self.instructions {
+            let effects = instruction.effects();
+            used.extend(effects.read_memory_cells);
+            used.extend(effects.written_memory_cells);
+        }
+        let mut unused: Vec<String> = self.runtime_args.memory_cells.keys()
+            .filter(|label| !used.contains(**label))
+            .map(|label| label.to_string())
+            .collect();
+        unused.sort();
+        unused
+    }
+
+    /// Returns the labels of all memory cells that are written by an instruction but never read by any instruction, sorted alphabetically.
+    /// 
+    /// Each write to such a cell is a dead store. Reads are counted regardless of their position in the program,
+    /// because a jump can lead back to an instruction in front of the write.
+    pub fn write_only_cells(&self) -> Vec<String> {
+        let mut read = HashSet::new();
+        let mut written = HashSet::new();
+        for instruction in &self.instructions {
+            let effects = instruction.effects();
+            read.extend(effects.read_memory_cells);
+            written.extend(effects.written_memory_cells);
+        }
+        let mut write_only: Vec<String> = written.difference(&read).cloned().collect();
+        write_only.sort();
+        write_only
+    }
+
+    /// Returns all labels that are not used by an instruction, sorted alphabetically.
+    /// 
+    /// A label is used when it is the target of a goto or call instruction or when its address is loaded.
+    pub fn unused_labels(&self) -> Vec<&'a str> {
+        let targets: HashSet<&str> = self.instructions.iter().filter_map(|instruction| instruction.referenced_label()).collect();
+        let mut unused: Vec<&'a str> = self.control_flow.instruction_labels.keys()
+            .filter(|label| !targets.contains(*label))
+            .copied()
+            .collect();
+        unused.sort();
+        unused
+    }
+
+    /// Returns the largest number of values that have been on the stack at the same time during execution.
+    pub fn max_stack_depth_reached(&self) -> usize {
+        self.max_stack_depth
+    }
+
+    /// Returns the indices of all accumulators that contain a value.
+    pub fn final_live_accumulators(&self) -> Vec<usize> {
+        self.runtime_args.accumulators.iter()
+            .enumerate()
+            .filter(|(_, accumulator)| accumulator.data.is_some())
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Returns the edges of the control flow graph of the program.
+    /// 
+    /// Goto instructions with a condition have an edge for the taken and for the not taken branch,
+    /// [Call](../instructions/enum.Instruction.html#variant.Call) has an edge to the subroutine and to the following instruction
+    /// and [Return](../instructions/enum.Instruction.html#variant.Return) and [GotoAccumulator](../instructions/enum.Instruction.html#variant.GotoAccumulator)
+    /// have no edges, because their target is only known at runtime.
+    /// Jumps to labels that don't exist have no edge.
+    /// 
+    /// Note: An edge to the index behind the last instruction means that the program ends.
+    pub fn cfg(&self) -> Vec<CfgEdge> {
+        let mut edges = Vec::new();
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            let target = instruction.goto_label().and_then(|label| self.control_flow.instruction_labels.get(label).copied());
+            match instruction {
+                Instruction::Goto(_) => edges.extend(target.map(|to| CfgEdge::new(index, to, CfgEdgeKind::Jump))),
+                Instruction::Call(_) => {
+                    edges.extend(target.map(|to| CfgEdge::new(index, to, CfgEdgeKind::Jump)));
+                    edges.push(CfgEdge::new(index, index + 1, CfgEdgeKind::FallThrough));
+                },
+                Instruction::Return() | Instruction::GotoAccumulator(_) => (),
+                _ => {
+                    if instruction.goto_label().is_some() {
+                        edges.extend(target.map(|to| CfgEdge::new(index, to, CfgEdgeKind::Taken)));
+                        edges.push(CfgEdge::new(index, index + 1, CfgEdgeKind::NotTaken));
+                    } else {
+                        edges.push(CfgEdge::new(index, index + 1, CfgEdgeKind::FallThrough));
+                    }
+                },
+            }
+        }
+        edges
+    }
+
+    /// Returns the [control flow graph](#method.cfg) of the program as Graphviz DOT digraph, that can be rendered with **dot**.
+    /// 
+    /// Each instruction is a node that is labeled with the instruction, edges to the index behind the last instruction
+    /// lead to an additional **end** node. Conditional jumps are labeled with **taken** and **not taken**.
+    pub fn cfg_dot(&self) -> String {
+        let mut dot = String::from("digraph cfg {\n");
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            let label = format!("{}: {}", index, instruction).replace('\\', "\\\\").replace('"', "\\\"");
+            dot.push_str(&format!("    n{} [label=\"{}\"];\n", index, label));
+        }
+        dot.push_str("    end [label=\"end\", shape=doublecircle];\n");
+        for edge in self.cfg() {
+            let to = if edge.to >= self.instructions.len() {
+                String::from("end")
+            } else {
+                format!("n{}", edge.to)
+            };
+            let attributes = match edge.kind {
+                CfgEdgeKind::FallThrough => "",
+                CfgEdgeKind::Jump => " [style=dashed]",
+                CfgEdgeKind::Taken => " [label=\"taken\"]",
+                CfgEdgeKind::NotTaken => " [label=\"not taken\"]",
+            };
+            dot.push_str(&format!("    n{} -> {}{};\n", edge.from, to, attributes));
+        }
+        dot.push('}');
+        dot
+    }
+
+    /// Returns the indices of the instructions that could fail at runtime together with the reason.
+    /// 
+    /// Flagged are divisions, jumps to and addresses of labels that don't exist and reads of accumulators or memory cells
+    /// that are empty at the start and not written by an instruction with a lower index.
+    /// Divisions by the constant 0 always fail and are flagged as **division by constant zero**,
+    /// divisions by another constant are not flagged.
+    /// An instruction can be listed multiple times.
+    pub fn potentially_failing(&self) -> Vec<(usize, &'static str)> {
+        let mut flagged = Vec::new();
+        self.for_each_with_written_values(|index, instruction, written_accumulators, written_memory_cells| {
+            match instruction {
+                Instruction::CalcAccumulatorWithConstant(Operation::Division, _, 0)
+                | Instruction::CalcMemoryCellWithMemoryCellConstant(Operation::Division, _, _, 0)
+                | Instruction::GotoIfDivisible(_, 0, _) => flagged.push((index, "division by constant zero")),
+                Instruction::CalcAccumulatorWithConstant(Operation::Division, _, _)
+                | Instruction::CalcMemoryCellWithMemoryCellConstant(Operation::Division, _, _, _) => (),
+                _ if matches!(instruction.operation(), Some(Operation::Division)) => flagged.push((index, "division by zero")),
+                _ => (),
+            }
+            if let Some(label) = instruction.referenced_label() {
+                if !self.control_flow.instruction_labels.contains_key(label) {
+                    flagged.push((index, "label missing"));
+                }
+            }
+            if !self.initial_runtime_args.treat_empty_as_zero && !reads_only_written_values(instruction, written_accumulators, written_memory_cells) {
+                flagged.push((index, "read of uninitialized value"));
+            }
+        });
+        flagged
+    }
+
+    /// Calls **f** for each instruction in the order they are listed, together with the accumulators and memory cells
+    /// that contain a value at the start or are written by an instruction with a lower index.
+    /// 
+    /// [ResetState](../instructions/enum.Instruction.html#variant.ResetState) clears the written accumulators and memory cells.
+    fn for_each_with_written_values(&self, mut f: impl FnMut(usize, &Instruction<'a>, &HashSet<usize>, &HashSet<String>)) {
+        let mut written_accumulators: HashSet<usize> = self.initial_runtime_args.accumulators.iter()
+            .enumerate()
+            .filter(|(_, accumulator)| accumulator.data.is_some())
+            .map(|(index, _)| index)
+            .collect();
+        let mut written_memory_cells: HashSet<String> = self.initial_runtime_args.memory_cells.iter()
+            .filter(|(_, memory_cell)| memory_cell.data.is_some())
+            .map(|(label, _)| label.to_string())
+            .collect();
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            f(index, instruction, &written_accumulators, &written_memory_cells);
+            if let Instruction::ResetState() = instruction {
+                written_accumulators.clear();
+                written_memory_cells.clear();
+            }
+            let effects = instruction.effects();
+            written_accumulators.extend(effects.written_accumulators);
+            written_memory_cells.extend(effects.written_memory_cells);
+        }
+    }
+
+    /// Checks without running the program that all accumulators, memory cells and labels used by the instructions exist.
+    /// 
+    /// Err contains each problem once, in the order the instructions are listed.
+    /// 
+    /// Note: Memory cells that are only accessed by [SpillStack](../instructions/enum.Instruction.html#variant.SpillStack) are not checked.
+    pub fn validate(&self) -> Result<(), Vec<RuntimeError>> {
+        let mut errors = Vec::new();
+        for instruction in &self.instructions {
+            let effects = instruction.effects();
+            let mut instruction_errors: Vec<RuntimeError> = effects.read_accumulators.iter()
+                .chain(effects.written_accumulators.iter())
+                .filter(|a_idx| **a_idx >= self.runtime_args.accumulators.len())
+                .map(|a_idx| RuntimeError::AccumulatorDoesNotExist(*a_idx))
+                .collect();
+            instruction_errors.extend(effects.read_memory_cells.iter()
+                .chain(effects.written_memory_cells.iter())
+                .filter(|label| !self.runtime_args.memory_cells.contains_key(label.as_str()))
+                .map(|label| RuntimeError::MemoryCellDoesNotExist(label.clone())));
+            if let Some(label) = instruction.referenced_label() {
+                if self.control_flow.label_index(label).is_none() {
+                    instruction_errors.push(RuntimeError::LabelMissing(label.to_string()));
+                }
+            }
+            for error in instruction_errors {
+                if !errors.contains(&error) {
+                    errors.push(error);
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Checks without running the program that all accumulators used by the instructions exist.
+    /// 
+    /// Returns the index of each instruction that uses an accumulator with an index equal to or above the number of accumulators
+    /// together with the index of that accumulator, each pair is listed once.
+    pub fn check_accumulator_bounds(&self) -> Vec<(usize, usize)> {
+        let mut flagged = Vec::new();
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            let effects = instruction.effects();
+            for a_idx in effects.read_accumulators.iter().chain(effects.written_accumulators.iter()) {
+                if *a_idx >= self.runtime_args.accumulators.len() && !flagged.contains(&(index, *a_idx)) {
+                    flagged.push((index, *a_idx));
+                }
+            }
+        }
+        flagged
+    }
+
+    /// Adds up how many values the instructions push onto and pop from the stack, going through the instructions in the order they are listed.
+    /// 
+    /// Ok contains the net number of values that are left on the stack, which is 0 for a balanced program.
+    /// Errors when more values could be popped than have been pushed before.
+    /// [ResetState](../instructions/enum.Instruction.html#variant.ResetState) clears the stack, so the count starts again at 0 after it.
+    /// 
+    /// Note: Jumps are ignored, so the result is only exact for programs without branches and loops.
+    /// Conditional pushes like [PushIf](../instructions/enum.Instruction.html#variant.PushIf) are counted as if they always push.
+    pub fn stack_balance(&self) -> Result<i32, String> {
+        let accumulators = i32::try_from(self.runtime_args.accessible_accumulators()).unwrap_or(i32::MAX);
+        let mut balance: i32 = 0;
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            balance = match instruction {
+                Instruction::Push() | Instruction::PushConstant(_) | Instruction::PushMemoryCell(_) | Instruction::PushIf(_, _, _) => balance.saturating_add(1),
+                Instruction::PushAllAccumulators() => balance.saturating_add(accumulators),
+                Instruction::Pop() | Instruction::PopMemoryCell(_) | Instruction::GotoIfStackPop(_, _, _) => balance - 1,
+                Instruction::PopN(n) => balance.saturating_sub(i32::try_from(*n).unwrap_or(i32::MAX)),
+                Instruction::PopAllAccumulators() => balance.saturating_sub(accumulators),
+                Instruction::ResetState() => 0,
+                _ => balance,
+            };
+            if balance < 0 {
+                return Err(format!("Stack could be empty when instruction {} ({}) pops a value!", index, instruction));
+            }
+        }
+        Ok(balance)
+    }
+
+    /// Returns the indices of the instructions that never change the accumulators, memory cells or the stack,
+    /// like **a0 := a0 + 0**, **a0 := a0** or **nop**.
+    /// 
+    /// An instruction is only flagged when the values it reads contain a value at the start or are written by an instruction with a lower index,
+    /// an empty value would make it fail or, with [treat_empty_as_zero](struct.RuntimeArgs.html#structfield.treat_empty_as_zero), set it to 0.
+    /// 
+    /// Note: Jumps are ignored, like in [potentially_failing](#method.potentially_failing).
+    pub fn find_redundant(&self) -> Vec<usize> {
+        let mut redundant = Vec::new();
+        self.for_each_with_written_values(|index, instruction, written_accumulators, written_memory_cells| {
+            let identity = match instruction {
+                Instruction::Nop() => true,
+                Instruction::AssignAccumulatorValueFromAccumulator(a_idx_a, a_idx_b) => a_idx_a == a_idx_b,
+                Instruction::AssingMemoryCellValueFromMemoryCell(label_a, label_b) => label_a == label_b,
+                Instruction::CalcAccumulatorWithConstant(operation, _, value) => operation.is_identity(*value),
+                Instruction::CalcMemoryCellWithMemoryCellConstant(operation, label_a, label_b, value) => label_a == label_b && operation.is_identity(*value),
+                Instruction::CalcAccumulatorWithAccumulator(Operation::Min | Operation::Max, a_idx_a, a_idx_b) => a_idx_a == a_idx_b,
+                _ => false,
+            };
+            if identity && reads_only_written_values(instruction, written_accumulators, written_memory_cells) {
+                redundant.push(index);
+            }
+        });
+        redundant
+    }
+
+    /// Returns simple metrics that describe how complex the program is, see [ComplexityReport].
+    /// 
+    /// Loops are detected by goto edges of the [control flow graph](#method.cfg) that jump backwards,
+    /// calls of subroutines that are defined earlier in the program are not loops.
+    pub fn complexity(&self) -> ComplexityReport {
+        let loops: Vec<(usize, usize)> = self.cfg().iter()
+            .filter(|edge| edge.to <= edge.from && matches!(edge.kind, CfgEdgeKind::Jump | CfgEdgeKind::Taken))
+            .filter(|edge| !matches!(self.instructions[edge.from], Instruction::Call(_)))
+            .map(|edge| (edge.to, edge.from))
+            .collect();
+        let max_nesting = (0..self.instructions.len())
+            .map(|index| loops.iter().filter(|(start, end)| *start <= index && index <= *end).count())
+            .max()
+            .unwrap_or(0);
+        ComplexityReport {
+            instructions: self.instructions.len(),
+            branches: self.instructions.iter()
+                .filter(|instruction| instruction.goto_label().is_some() && !matches!(instruction, Instruction::Goto(_) | Instruction::Call(_)))
+                .count(),
+            loops: loops.len(),
+            max_nesting,
+        }
+    }
+
+    /// Returns the shortest sequence of instruction indices that leads from the first instruction to the instruction at **index**,
+    /// following the edges of the [control flow graph](#method.cfg). Both the first instruction and **index** are included.
+    /// 
+    /// Returns None when the instruction can not be reached.
+    pub fn path_to(&self, index: usize) -> Option<Vec<usize>> {
+        let edges = self.cfg();
+        let mut previous: HashMap<usize, usize> = HashMap::new();
+        let mut queue = VecDeque::from([0]);
+        let mut visited = HashSet::from([0]);
+        while let Some(current) = queue.pop_front() {
+            if current == index {
+                let mut path = vec![index];
+                while let Some(prev) = previous.get(path.last().unwrap()) {
+                    path.push(*prev);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for edge in edges.iter().filter(|edge| edge.from == current) {
+                if visited.insert(edge.to) {
+                    previous.insert(edge.to, current);
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the accumulators and memory cells that have been read while they were empty.
+    /// 
+    /// Only recorded when [treat_empty_as_zero](struct.RuntimeArgs.html#structfield.treat_empty_as_zero) is set,
+    /// otherwise reading an empty accumulator or memory cell is an error.
+    pub fn uninitialized_reads(&self) -> Vec<String> {
+        self.runtime_args.uninitialized_reads.clone()
+    }
+
     /// Returns reference to **runtime_args**.
-    pub fn runtime_args(&self) -> &RuntimeArgs {
+    pub fn runtime_args(&self) -> &RuntimeArgs<'a> {
         &self.runtime_args
     }
 
 }
 
+/// The instructions and labels of a program, see [program_from_json](struct.Runner.html#method.program_from_json).
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[allow(dead_code)]
+struct Program<'a> {
+    #[serde(borrow)]
+    instructions: Vec<Instruction<'a>>,
+    #[serde(borrow)]
+    labels: HashMap<&'a str, usize>,
+}
+
+/// The result of a finished program run, see [run_full](struct.Runner.html#method.run_full).
+#[derive(Clone)]
+#[allow(dead_code)]
+pub struct RunOutcome<'a> {
+    /// The lines that have been printed by the print instructions
+    pub output: Vec<String>,
+    /// The state of the accumulators, memory cells and the stack after the program finished
+    pub runtime_args: RuntimeArgs<'a>,
+    /// The number of instructions that have been executed
+    pub executed_instructions: usize,
+}
+
+/// Everything that is needed to continue running a program, see [checkpoint](struct.Runner.html#method.checkpoint).
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(dead_code)]
+pub struct Checkpoint<'a> {
+    /// The instructions of the program
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub instructions: Vec<Instruction<'a>>,
+    /// Label to instruction index mappings
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub labels: HashMap<&'a str, usize>,
+    /// The state of the accumulators, memory cells and the stack
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub runtime_args: RuntimeArgs<'a>,
+    /// The index of the instruction that is executed next
+    pub next_instruction_index: usize,
+    /// Indices of the instructions to return to from the currently running subroutines
+    pub call_stack: Vec<usize>,
+}
+
+/// The executed instructions of a program run, see [trace](struct.Runner.html#method.trace).
+pub type Trace = Vec<TraceStep>;
+
+/// A single executed instruction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceStep {
+    /// Index of the executed instruction
+    pub instruction_index: usize,
+    /// The changes the instruction made to the state, see [diff_report](struct.RuntimeArgs.html#method.diff_report)
+    pub changes: String,
+}
+
+/// Metrics of a program, see [complexity](struct.Runner.html#method.complexity).
+#[derive(Debug, Clone, PartialEq)]
+#[allow(dead_code)]
+pub struct ComplexityReport {
+    /// Number of instructions
+    pub instructions: usize,
+    /// Number of conditional jumps
+    pub branches: usize,
+    /// Number of jumps that go backwards
+    pub loops: usize,
+    /// Largest number of loops an instruction is contained in
+    pub max_nesting: usize,
+}
+
+/// An edge in the control flow graph of a program, see [cfg](struct.Runner.html#method.cfg).
+#[derive(Debug, Clone, PartialEq)]
+#[allow(dead_code)]
+pub struct CfgEdge {
+    /// Index of the instruction the edge starts at
+    pub from: usize,
+    /// Index of the instruction the edge leads to
+    pub to: usize,
+    pub kind: CfgEdgeKind,
+}
+
+#[allow(dead_code)]
+impl CfgEdge {
+    pub fn new(from: usize, to: usize, kind: CfgEdgeKind) -> Self {
+        Self {
+            from,
+            to,
+            kind,
+        }
+    }
+}
+
+/// The different ways how the next instruction can be reached.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(dead_code)]
+pub enum CfgEdgeKind {
+    /// The next instruction is executed
+    FallThrough,
+    /// Unconditional jump to a label
+    Jump,
+    /// Conditional jump to a label when the condition is true
+    Taken,
+    /// The next instruction is executed when the condition is false
+    NotTaken,
+}
+
 /// Used to control what instruction should be executed next.
+#[derive(Clone)]
 pub struct ControlFlow<'a> {
     /// The index of the instruction that should be executed next in the **instructions** vector.
     pub next_instruction_index: usize,
@@ -76,6 +1175,10 @@ pub struct ControlFlow<'a> {
     /// 
     /// Value = index of the instruction in the instructions vector
     pub instruction_labels: HashMap<&'a str, usize>,
+    /// Indices of the instructions to return to from the currently running subroutines.
+    pub call_stack: Vec<usize>,
+    /// When set, labels are looked up ignoring their case, so that **Loop** resolves a label stored as **loop**.
+    pub case_insensitive_labels: bool,
 }
 
 impl<'a> ControlFlow<'a> {
@@ -84,30 +1187,68 @@ impl<'a> ControlFlow<'a> {
         Self {
             next_instruction_index: 0,
             instruction_labels: HashMap::new(),
+            call_stack: Vec::new(),
+            case_insensitive_labels: false,
         }
     }
 
     /// Updates **next_instruction_index** if **label** is contained in **instruction_labels**,
     /// otherwise returns an error.
-    pub fn next_instruction_index(&mut self, label: &str) -> Result<(), String> {
-        if let Some(index) = self.instruction_labels.get(label) {
-            self.next_instruction_index = *index;
+    pub fn next_instruction_index(&mut self, label: &str) -> Result<(), RuntimeError> {
+        if let Some(index) = self.label_index(label) {
+            self.next_instruction_index = index;
             Ok(())
         } else {
-            Err(format!("Unable to update instruction index: no index found for label {}", label))
+            Err(RuntimeError::LabelMissing(label.to_string()))
         }
     }
+
+    /// Returns the instruction index of **label**, respecting **case_insensitive_labels**.
+    /// 
+    /// An exact match is preferred over a match that only differs in case.
+    pub fn label_index(&self, label: &str) -> Option<usize> {
+        if let Some(index) = self.instruction_labels.get(label) {
+            return Some(*index);
+        }
+        if !self.case_insensitive_labels {
+            return None;
+        }
+        let label = label.to_lowercase();
+        self.instruction_labels.iter()
+            .find(|(key, _)| key.to_lowercase() == label)
+            .map(|(_, index)| *index)
+    }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RuntimeArgs<'a> {
     /// Current values stored in accumulators
     pub accumulators: Vec<Accumulator>,
     /// All registers that are used to store data
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub memory_cells: HashMap<&'a str, MemoryCell>,
     /// The stack of the runner
     pub stack: Vec<i32>,
+    /// Disables the output of all print instructions when set
+    pub quiet: bool,
+    /// Output of the print instructions, when set the output is stored here instead of being printed into the console
+    pub captured_output: Option<Vec<String>>,
+    /// Value that memory cells and accumulators added with [add_storage_cell](#method.add_storage_cell)
+    /// and [add_accumulator](#method.add_accumulator) start with
+    #[allow(dead_code)]
+    pub default_cell_value: Option<i32>,
+    /// When set, reading an empty accumulator or memory cell returns 0 and is recorded in **uninitialized_reads** instead of failing
+    pub treat_empty_as_zero: bool,
+    /// Accumulators (e.g. a0) and memory cells (e.g. p(a)) that have been read while empty, in the order they were read
+    pub uninitialized_reads: Vec<String>,
+    /// When set, accessing an accumulator with a higher index fails, even when the accumulator exists.
+    /// Instructions that access all accumulators, like [PushAllAccumulators](../instructions/enum.Instruction.html#variant.PushAllAccumulators),
+    /// only access the accumulators up to and including the limit.
+    pub accumulator_soft_limit: Option<usize>,
 }
 
+#[allow(dead_code)]
 impl<'a> RuntimeArgs<'a> {
     pub fn new() -> Self {
         let mut accumulators = Vec::new();
@@ -125,6 +1266,12 @@ impl<'a> RuntimeArgs<'a> {
             accumulators,
             memory_cells,
             stack: Vec::new(),
+            quiet: false,
+            captured_output: None,
+            default_cell_value: None,
+            treat_empty_as_zero: false,
+            uninitialized_reads: Vec::new(),
+            accumulator_soft_limit: None,
         }
     }
 
@@ -134,20 +1281,1297 @@ impl<'a> RuntimeArgs<'a> {
             accumulators: Vec::new(),
             memory_cells: HashMap::new(),
             stack: Vec::new(),
+            quiet: false,
+            captured_output: None,
+            default_cell_value: None,
+            treat_empty_as_zero: false,
+            uninitialized_reads: Vec::new(),
+            accumulator_soft_limit: None,
         }
     }
 
     /// Creates a new memory cell with label **label** if it does not already exist
     /// and adds it to the **memory_cells* hashmap.
+    /// 
+    /// The memory cell starts with **default_cell_value**.
     pub fn add_storage_cell(&mut self, label: &'a str) {
         if !self.memory_cells.contains_key(label) {
-            self.memory_cells.insert(label, MemoryCell::new(label));
+            let mut memory_cell = MemoryCell::new(label);
+            memory_cell.data = self.default_cell_value;
+            self.memory_cells.insert(label, memory_cell);
         }
     }
 
+    /// Returns a table of all accumulators, memory cells and the stack that have a different value in **other**,
+    /// each line shows the value in **self** and the value in **other**.
+    /// 
+    /// Memory cells are sorted by label.
+    pub fn diff_report(&self, other: &RuntimeArgs) -> String {
+        let mut report = String::new();
+        for i in 0..self.accumulators.len().max(other.accumulators.len()) {
+            let before = self.accumulators.get(i).and_then(|a| a.data);
+            let after = other.accumulators.get(i).and_then(|a| a.data);
+            if before != after {
+                report.push_str(&format!("a{}: {} -> {}\n", i, format_value(before), format_value(after)));
+            }
+        }
+        let mut labels: Vec<&str> = self.memory_cells.keys().chain(other.memory_cells.keys()).copied().collect();
+        labels.sort();
+        labels.dedup();
+        for label in labels {
+            let before = self.memory_cells.get(label).and_then(|m| m.data);
+            let after = other.memory_cells.get(label).and_then(|m| m.data);
+            if before != after {
+                report.push_str(&format!("p({}): {} -> {}\n", label, format_value(before), format_value(after)));
+            }
+        }
+        if self.stack != other.stack {
+            report.push_str(&format!("stack: {:?} -> {:?}\n", self.stack, other.stack));
+        }
+        report
+    }
+
+    /// Sets the value of the accumulator with index **index**.
+    /// 
+    /// Errors when the accumulator does not exist.
+    pub fn set_accumulator(&mut self, index: usize, value: i32) -> Result<(), RuntimeError> {
+        let accumulator = self.accumulators.get_mut(index).ok_or(RuntimeError::AccumulatorDoesNotExist(index))?;
+        accumulator.data = Some(value);
+        Ok(())
+    }
+
+    /// Returns the values of all accumulators, see [restore_accumulators](#method.restore_accumulators).
+    pub fn accumulator_snapshot(&self) -> Vec<Option<i32>> {
+        self.accumulators.iter().map(|a| a.data).collect()
+    }
+
+    /// Sets the accumulators to the values from **snapshot** that has been created with [accumulator_snapshot](#method.accumulator_snapshot).
+    /// 
+    /// Values for accumulators that do not exist are ignored.
+    pub fn restore_accumulators(&mut self, snapshot: &[Option<i32>]) {
+        for (accumulator, value) in self.accumulators.iter_mut().zip(snapshot) {
+            accumulator.data = *value;
+        }
+    }
+
+    /// Returns the accumulators and memory cells as csv with the columns **name** and **value**, empty values are left blank.
+    /// 
+    /// Accumulators are listed first, memory cells are sorted by label.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("name,value\n");
+        for (index, accumulator) in self.accumulators.iter().enumerate() {
+            csv.push_str(&format!("a{},{}\n", index, accumulator.data.map_or(String::new(), |v| v.to_string())));
+        }
+        let mut labels: Vec<&&str> = self.memory_cells.keys().collect();
+        labels.sort();
+        for label in labels {
+            let data = self.memory_cells[*label].data;
+            csv.push_str(&format!("p({}),{}\n", label, data.map_or(String::new(), |v| v.to_string())));
+        }
+        csv
+    }
+
+    /// Returns a hash of the values in the accumulators, memory cells and the stack.
+    /// 
+    /// Memory cells are hashed sorted by label, so that equal states always have the same hash.
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for accumulator in &self.accumulators {
+            accumulator.data.hash(&mut hasher);
+        }
+        let mut memory_cells: Vec<(&&str, &MemoryCell)> = self.memory_cells.iter().collect();
+        memory_cells.sort_by_key(|(label, _)| **label);
+        for (label, memory_cell) in memory_cells {
+            label.hash(&mut hasher);
+            memory_cell.data.hash(&mut hasher);
+        }
+        self.stack.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns the value on top of the stack or **None** when the stack is empty.
+    pub fn stack_top(&self) -> Option<i32> {
+        self.stack.last().copied()
+    }
+
     /// Adds a new accumulator to the accumulators vector.
+    /// 
+    /// The accumulator starts with **default_cell_value**.
     pub fn add_accumulator(&mut self) {
         let id = self.accumulators.len();
-        self.accumulators.push(Accumulator::new(id as i32));
+        let mut accumulator = Accumulator::new(id as i32);
+        accumulator.data = self.default_cell_value;
+        self.accumulators.push(accumulator);
+    }
+
+    /// Returns the number of accumulators that can be accessed, that are all accumulators up to and including the
+    /// [accumulator_soft_limit](#structfield.accumulator_soft_limit).
+    pub fn accessible_accumulators(&self) -> usize {
+        match self.accumulator_soft_limit {
+            Some(limit) => self.accumulators.len().min(limit.saturating_add(1)),
+            None => self.accumulators.len(),
+        }
+    }
+}
+
+/// Formats the value of an accumulator or memory cell, empty values are shown as **None**.
+pub fn format_value(value: Option<i32>) -> String {
+    value.map_or(String::from("None"), |v| v.to_string())
+}
+
+/// Returns true when all accumulators and memory cells that **instruction** reads are contained in **written_accumulators** and **written_memory_cells**.
+fn reads_only_written_values(instruction: &Instruction, written_accumulators: &HashSet<usize>, written_memory_cells: &HashSet<String>) -> bool {
+    let effects = instruction.effects();
+    effects.read_accumulators.iter().all(|a_idx| written_accumulators.contains(a_idx))
+        && effects.read_memory_cells.iter().all(|label| written_memory_cells.contains(label))
+}
+
+/// Returns the index of the first step in which the traces execute a different instruction or change the state differently.
+/// 
+/// When one trace is longer than the other, they differ at the first step that is missing in the shorter trace.
+/// Returns None when both traces are equal.
+#[allow(dead_code)]
+pub fn diff_traces(a: &Trace, b: &Trace) -> Option<usize> {
+    a.iter()
+        .zip(b.iter())
+        .position(|(step_a, step_b)| step_a != step_b)
+        .or_else(|| if a.len() != b.len() { Some(a.len().min(b.len())) } else { None })
+}
+
+/// Runs [validate](struct.Runner.html#method.validate) for each program, the programs use the default runtime args and don't have labels.
+#[allow(dead_code)]
+pub fn validate_programs(programs: &[Vec<Instruction>]) -> Vec<Result<(), Vec<RuntimeError>>> {
+    programs.iter().map(|program| Runner::new(program.clone()).validate()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::HashMap, rc::Rc, time::Duration};
+
+    use crate::{instructions::Instruction, runtime::{diff_traces, validate_programs, Runner, RuntimeArgs, CfgEdge, CfgEdgeKind, ComplexityReport}, base::{Operation, Comparison, RuntimeError}};
+
+    #[test]
+    fn test_terminates_within() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 1),
+        ];
+        let mut runner = Runner::new(instructions);
+        assert!(runner.terminates_within(10));
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 1),
+            Instruction::Goto("loop"),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 1).unwrap();
+        assert!(!runner.terminates_within(100));
+    }
+
+    #[test]
+    fn test_link() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::AssignMemoryCellValue("a", 8),
+            Instruction::CalcAccumulatorWithConstant(Operation::Multiplication, 0, 2),
+            Instruction::CalcMemoryCellWithMemoryCellConstant(Operation::Minus, "a", "a", 1),
+            Instruction::AssignAccumulatorValueFromMemoryCell(1, "a"),
+            Instruction::GotoIfConstant(Comparison::More, "loop", 1, 0),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 2).unwrap();
+        runner.link();
+        runner.run().unwrap();
+        assert_eq!(runner.runtime_args().accumulators[0].data.unwrap(), 256);
+    }
+
+    #[test]
+    fn test_coverage() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::AssignMemoryCellValue("a", 8),
+            Instruction::CalcAccumulatorWithConstant(Operation::Multiplication, 0, 2),
+            Instruction::CalcMemoryCellWithMemoryCellConstant(Operation::Minus, "a", "a", 1),
+            Instruction::AssignAccumulatorValueFromMemoryCell(1, "a"),
+            Instruction::GotoIfConstant(Comparison::More, "loop", 1, 0),
+            Instruction::PrintMemoryCells(),
+            Instruction::PrintAccumulators(),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 2).unwrap();
+        assert_eq!(runner.coverage(), vec![0; 8]);
+        runner.run().unwrap();
+        assert_eq!(runner.coverage(), vec![1, 1, 8, 8, 8, 8, 1, 1]);
+    }
+
+    #[test]
+    fn test_set_start_index() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::AssignAccumulatorValue(1, 2),
+            Instruction::AssignAccumulatorValue(2, 3),
+        ];
+        let mut runner = Runner::new(instructions);
+        assert!(runner.set_start_index(3).is_err());
+        runner.set_start_index(2).unwrap();
+        runner.run().unwrap();
+        assert!(runner.runtime_args().accumulators[0].data.is_none());
+        assert!(runner.runtime_args().accumulators[1].data.is_none());
+        assert_eq!(runner.runtime_args().accumulators[2].data.unwrap(), 3);
+    }
+
+    #[test]
+    fn test_unused_cells() {
+        let mut runtime_args = RuntimeArgs::new_empty();
+        runtime_args.add_accumulator();
+        runtime_args.add_storage_cell("a");
+        runtime_args.add_storage_cell("b");
+        runtime_args.add_storage_cell("c");
+        let instructions = vec![
+            Instruction::AssignMemoryCellValue("a", 5),
+            Instruction::AssignAccumulatorValueFromMemoryCell(0, "a"),
+            Instruction::CalcAccumulatorWithMemoryCell(Operation::Plus, 0, "c"),
+        ];
+        let runner = Runner::new_custom(instructions, runtime_args);
+        assert_eq!(runner.unused_cells(), vec![String::from("b")]);
+    }
+
+    #[test]
+    fn test_write_only_cells() {
+        let mut runtime_args = RuntimeArgs::new_empty();
+        runtime_args.add_accumulator();
+        runtime_args.add_storage_cell("a");
+        runtime_args.add_storage_cell("b");
+        runtime_args.add_storage_cell("c");
+        let instructions = vec![
+            Instruction::AssignMemoryCellValue("a", 5),
+            Instruction::AssignAccumulatorValueFromMemoryCell(0, "a"),
+            Instruction::AssignMemoryCellValueFromAccumulator("b", 0),
+        ];
+        let runner = Runner::new_custom(instructions, runtime_args);
+        assert_eq!(runner.write_only_cells(), vec![String::from("b")]);
+        assert_eq!(runner.unused_cells(), vec![String::from("c")]);
+    }
+
+    #[test]
+    fn test_max_stack_depth_reached() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::Push(),
+            Instruction::Push(),
+            Instruction::Push(),
+            Instruction::Pop(),
+            Instruction::Pop(),
+            Instruction::Pop(),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.run().unwrap();
+        assert!(runner.runtime_args().stack.is_empty());
+        assert_eq!(runner.max_stack_depth_reached(), 3);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_labels_sorted() {
+        let mut runner = Runner::new((0..6).map(|i| Instruction::AssignAccumulatorValue(0, i)).collect());
+        runner.add_label("end", 5).unwrap();
+        runner.add_label("loop", 2).unwrap();
+        runner.add_label("body", 2).unwrap();
+        assert_eq!(runner.labels_sorted(), vec![("body", 2), ("loop", 2), ("end", 5)]);
+    }
+
+    #[test]
+    fn test_clone() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::AssignAccumulatorValue(1, 2),
+            Instruction::CalcAccumulatorWithAccumulator(Operation::Plus, 0, 1),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.run_with_limit(2).unwrap();
+        let mut branch = runner.clone();
+        branch.runtime_args.accumulators[1].data = Some(10);
+        runner.run().unwrap();
+        branch.run().unwrap();
+        assert_eq!(runner.runtime_args().accumulators[0].data.unwrap(), 3);
+        assert_eq!(branch.runtime_args().accumulators[0].data.unwrap(), 11);
+        assert_eq!(runner.coverage(), vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn test_step_back() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::AssignAccumulatorValue(0, 2),
+            Instruction::AssignAccumulatorValue(0, 3),
+        ];
+        let mut runner = Runner::new(instructions);
+        assert!(runner.step_back().is_err());
+        runner.enable_history(1);
+        runner.step().unwrap();
+        runner.step().unwrap();
+        assert_eq!(runner.runtime_args().accumulators[0].data.unwrap(), 2);
+        runner.step_back().unwrap();
+        assert_eq!(runner.runtime_args().accumulators[0].data.unwrap(), 1);
+        assert!(runner.step_back().is_err());
+        runner.run().unwrap();
+        assert_eq!(runner.runtime_args().accumulators[0].data.unwrap(), 3);
+    }
+
+    #[test]
+    fn test_extract_subroutine() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 3),
+            Instruction::Call("double"),
+            Instruction::Goto("end"),
+            Instruction::CalcAccumulatorWithConstant(Operation::Multiplication, 0, 2),
+            Instruction::Return(),
+            Instruction::PrintAccumulators(),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("double", 3).unwrap();
+        runner.add_label("end", 5).unwrap();
+        runner.add_label("no_return", 5).unwrap();
+        let subroutine = runner.extract_subroutine("double").unwrap();
+        assert_eq!(subroutine.len(), 2);
+        assert!(matches!(subroutine[0], Instruction::CalcAccumulatorWithConstant(Operation::Multiplication, 0, 2)));
+        assert!(matches!(subroutine[1], Instruction::Return()));
+        assert!(runner.extract_subroutine("no_return").is_err());
+        assert!(runner.extract_subroutine("missing").is_err());
+        runner.run().unwrap();
+        assert_eq!(runner.runtime_args().accumulators[0].data.unwrap(), 6);
+    }
+
+    #[test]
+    fn test_run_capturing() {
+        let instructions = vec![
+            Instruction::PushConstant(5),
+            Instruction::PrintStack(),
+            Instruction::PushConstant(7),
+            Instruction::PrintStack(),
+        ];
+        let mut runner = Runner::new(instructions);
+        let expected = vec![
+            "------ Stack -------", "0 - 5", "--------------------",
+            "------ Stack -------", "0 - 5", "1 - 7", "--------------------",
+        ];
+        assert_eq!(runner.run_capturing().unwrap(), expected);
+        assert!(runner.runtime_args().captured_output.is_none());
+    }
+
+    #[test]
+    fn test_run_full() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 5),
+            Instruction::PrintLabeled("value", 0),
+            Instruction::PushConstant(7),
+        ];
+        let mut runner = Runner::new(instructions);
+        let outcome = runner.run_full().unwrap();
+        assert_eq!(outcome.output, vec!["value: 5"]);
+        assert_eq!(outcome.runtime_args.accumulators[0].data, Some(5));
+        assert_eq!(outcome.runtime_args.stack, vec![7]);
+        assert!(outcome.runtime_args.captured_output.is_none());
+        assert_eq!(outcome.executed_instructions, 3);
+    }
+
+    #[test]
+    fn test_add_label_out_of_bounds() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::Goto("end"),
+            Instruction::AssignAccumulatorValue(0, 2),
+        ];
+        let mut runner = Runner::new(instructions);
+        let err = runner.add_label("end", 100);
+        assert_eq!(err, Err(String::from("Unable to add label end, index 100 is out of bounds!")));
+        assert!(runner.add_label("end", 3).is_err());
+        assert!(runner.labels_sorted().is_empty());
+        assert!(runner.add_label("end", 2).is_ok());
+    }
+
+    #[test]
+    fn test_run_batch() {
+        let instructions = vec![
+            Instruction::CalcAccumulatorWithConstant(Operation::Multiplication, 0, 2),
+        ];
+        let mut runner = Runner::new(instructions);
+        let results = runner.run_batch(&[vec![(0, 1)], vec![(0, 5)], vec![(0, -3)], vec![], vec![(10, 1)]]);
+        let values: Vec<Option<i32>> = results[..3].iter().map(|result| result.as_ref().unwrap().accumulators[0].data).collect();
+        assert_eq!(values, vec![Some(2), Some(10), Some(-6)]);
+        assert_eq!(results[3].as_ref().err(), Some(&RuntimeError::AccumulatorUninitialized(0)));
+        assert_eq!(results[4].as_ref().err(), Some(&RuntimeError::AccumulatorDoesNotExist(10)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::PushConstant(1),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.run().unwrap();
+        runner.reset();
+        assert!(runner.runtime_args().accumulators[0].data.is_none());
+        assert!(runner.runtime_args().stack.is_empty());
+        assert_eq!(runner.coverage(), vec![0, 0]);
+        assert!(!runner.finished());
+    }
+
+    #[test]
+    fn test_diff_report() {
+        let mut before = RuntimeArgs::new_empty();
+        before.add_accumulator();
+        before.add_storage_cell("a");
+        before.add_storage_cell("b");
+        before.memory_cells.get_mut("a").unwrap().data = Some(5);
+        let mut after = before.clone();
+        assert!(before.diff_report(&after).is_empty());
+        after.memory_cells.get_mut("a").unwrap().data = Some(8);
+        assert_eq!(before.diff_report(&after), "p(a): 5 -> 8\n");
+        after.accumulators[0].data = Some(1);
+        assert_eq!(before.diff_report(&after), "a0: None -> 1\np(a): 5 -> 8\n");
+    }
+
+    #[test]
+    fn test_final_live_accumulators() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(1, 5),
+            Instruction::AssignAccumulatorValueFromAccumulator(3, 1),
+        ];
+        let mut runner = Runner::new(instructions);
+        assert!(runner.final_live_accumulators().is_empty());
+        runner.run().unwrap();
+        assert_eq!(runner.final_live_accumulators(), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_insert_instruction() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::AssignMemoryCellValue("a", 8),
+            Instruction::CalcAccumulatorWithConstant(Operation::Multiplication, 0, 2),
+            Instruction::CalcMemoryCellWithMemoryCellConstant(Operation::Minus, "a", "a", 1),
+            Instruction::AssignAccumulatorValueFromMemoryCell(1, "a"),
+            Instruction::GotoIfConstant(Comparison::More, "loop", 1, 0),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 2).unwrap();
+        assert!(runner.insert_instruction(7, Instruction::Nop()).is_err());
+        runner.insert_instruction(2, Instruction::Nop()).unwrap();
+        assert_eq!(runner.labels_sorted(), vec![("loop", 3)]);
+        runner.run().unwrap();
+        assert_eq!(runner.runtime_args().accumulators[0].data.unwrap(), 256);
+        assert_eq!(runner.coverage()[2], 1);
+    }
+
+    #[test]
+    fn test_remove_instruction() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::Nop(),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 1),
+            Instruction::GotoIfConstant(Comparison::Less, "loop", 0, 5),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 2).unwrap();
+        runner.add_label("start", 0).unwrap();
+        assert!(runner.remove_instruction(4).is_err());
+        assert!(runner.remove_instruction(0).is_err());
+        assert!(matches!(runner.remove_instruction(1), Ok(Instruction::Nop())));
+        assert_eq!(runner.labels_sorted(), vec![("start", 0), ("loop", 1)]);
+        runner.run().unwrap();
+        assert_eq!(runner.runtime_args().accumulators[0].data.unwrap(), 5);
+        assert_eq!(runner.coverage(), vec![1, 4, 4]);
+    }
+
+    #[test]
+    fn test_run_to_label() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 0),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 1),
+            Instruction::GotoIfConstant(Comparison::Less, "loop", 0, 3),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 1).unwrap();
+        assert_eq!(runner.run_to_label("missing"), Err(RuntimeError::LabelMissing(String::from("missing"))));
+        runner.run_to_label("loop").unwrap();
+        assert_eq!(runner.next_instruction_index(), 1);
+        assert_eq!(runner.runtime_args().accumulators[0].data.unwrap(), 0);
+        runner.run_to_label("loop").unwrap();
+        assert_eq!(runner.next_instruction_index(), 1);
+        assert_eq!(runner.runtime_args().accumulators[0].data.unwrap(), 1);
+        runner.run().unwrap();
+        assert!(runner.finished());
+    }
+
+    #[test]
+    fn test_default_cell_value() {
+        let mut runtime_args = RuntimeArgs::new_empty();
+        runtime_args.add_accumulator();
+        runtime_args.add_storage_cell("a");
+        runtime_args.default_cell_value = Some(0);
+        runtime_args.add_accumulator();
+        runtime_args.add_storage_cell("b");
+        assert!(runtime_args.accumulators[0].data.is_none());
+        assert!(runtime_args.memory_cells.get("a").unwrap().data.is_none());
+        let instructions = vec![
+            Instruction::CalcAccumulatorWithMemoryCell(Operation::Plus, 1, "b"),
+        ];
+        let mut runner = Runner::new_custom(instructions, runtime_args);
+        runner.run().unwrap();
+        assert_eq!(runner.runtime_args().accumulators[1].data.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_cfg() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 0),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 1),
+            Instruction::GotoIfConstant(Comparison::Less, "loop", 0, 3),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 1).unwrap();
+        assert_eq!(runner.cfg(), vec![
+            CfgEdge::new(0, 1, CfgEdgeKind::FallThrough),
+            CfgEdge::new(1, 2, CfgEdgeKind::FallThrough),
+            CfgEdge::new(2, 1, CfgEdgeKind::Taken),
+            CfgEdge::new(2, 3, CfgEdgeKind::NotTaken),
+        ]);
+    }
+
+    #[test]
+    fn test_cfg_dot() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 0),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 1),
+            Instruction::GotoIfConstant(Comparison::Less, "loop", 0, 3),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 1).unwrap();
+        let dot = runner.cfg_dot();
+        assert!(dot.starts_with("digraph cfg {\n"));
+        assert!(dot.ends_with('}'));
+        assert_eq!(dot.lines().filter(|line| line.contains("[label=") && !line.contains("->")).count(), 4);
+        assert_eq!(dot.lines().filter(|line| line.contains("->")).count(), 4);
+        assert!(dot.contains("    n0 [label=\"0: a0 := 0\"];"));
+        assert!(dot.contains("    n2 -> n1 [label=\"taken\"];"));
+        assert!(dot.contains("    n2 -> end [label=\"not taken\"];"));
+    }
+
+    #[test]
+    fn test_uninitialized_reads() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValueFromMemoryCell(0, "a"),
+            Instruction::CalcAccumulatorWithAccumulator(Operation::Plus, 0, 1),
+        ];
+        let mut runtime_args = RuntimeArgs::new();
+        runtime_args.treat_empty_as_zero = true;
+        let mut runner = Runner::new_custom(instructions, runtime_args);
+        runner.run().unwrap();
+        assert_eq!(runner.uninitialized_reads(), vec!["p(a)", "a1"]);
+        assert_eq!(runner.runtime_args().accumulators[0].data.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_append_program() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 3),
+            Instruction::Call("lib_double"),
+            Instruction::Goto("lib_finish"),
+        ];
+        // both programs use the label end
+        let library = vec![
+            Instruction::AssignMemoryCellValue("counter", 2),
+            Instruction::CalcAccumulatorWithConstant(Operation::Multiplication, 0, 2),
+            Instruction::CalcMemoryCellWithMemoryCellConstant(Operation::Minus, "counter", "counter", 1),
+            Instruction::GotoIfMemoryCellConstant(Comparison::Equal, "end", "counter", 0),
+            Instruction::Goto("loop"),
+            Instruction::Return(),
+            Instruction::Nop(),
+        ];
+        let mut runtime_args = RuntimeArgs::new_empty();
+        runtime_args.add_accumulator();
+        let mut runner = Runner::new_custom(instructions, runtime_args);
+        runner.add_label("end", 2).unwrap();
+        assert!(runner.append_program(library.clone(), &[("lib_double", 7)], "lib_").is_err());
+        assert!(runner.append_program(library.clone(), &[("double", 0)], "lib_").is_err());
+        assert!(runner.append_program(library.clone(), &[("end", 5)], "").is_err());
+        // nothing is added when a later label is invalid
+        assert!(runner.append_program(library.clone(), &[("lib_double", 0), ("lib_end", 7)], "lib_").is_err());
+        assert_eq!(runner.labels_sorted(), vec![("end", 2)]);
+        assert!(!runner.runtime_args().memory_cells.contains_key("counter"));
+        runner.append_program(library, &[("lib_double", 0), ("lib_loop", 1), ("lib_end", 5), ("lib_finish", 6)], "lib_").unwrap();
+        assert_eq!(runner.labels_sorted(), vec![("end", 2), ("lib_double", 3), ("lib_loop", 4), ("lib_end", 8), ("lib_finish", 9)]);
+        assert!(runner.runtime_args().memory_cells.contains_key("counter"));
+        runner.run().unwrap();
+        assert_eq!(runner.runtime_args().accumulators[0].data.unwrap(), 12);
+        assert_eq!(runner.runtime_args().memory_cells["counter"].data, Some(0));
+    }
+
+    #[test]
+    fn test_trace_markdown() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 5),
+            Instruction::AssignMemoryCellValue("a", 2),
+            Instruction::CalcAccumulatorWithMemoryCell(Operation::Multiplication, 0, "a"),
+            Instruction::Nop(),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.enable_trace();
+        runner.run().unwrap();
+        assert_eq!(runner.trace().len(), 4);
+        let expected = "| Step | Instruction | Changes |\n\
+            |------|-------------|---------|\n\
+            | 1 | a0 := 5 | a0: None -> 5 |\n\
+            | 2 | p(a) := 2 | p(a): None -> 2 |\n\
+            | 3 | a0 := a0 * p(a) | a0: 5 -> 10 |\n\
+            | 4 | nop |  |\n";
+        assert_eq!(runner.trace_markdown(), expected);
+    }
+
+    #[test]
+    fn test_behaves_like() {
+        let mut multiplication = Runner::new(vec![
+            Instruction::CalcAccumulatorWithConstant(Operation::Multiplication, 0, 2),
+        ]);
+        let mut addition = Runner::new(vec![
+            Instruction::AssignAccumulatorValueFromAccumulator(1, 0),
+            Instruction::CalcAccumulatorWithAccumulator(Operation::Plus, 0, 1),
+            Instruction::AssignAccumulatorValue(1, 0),
+        ]);
+        let mut square = Runner::new(vec![
+            Instruction::CalcAccumulatorWithAccumulator(Operation::Multiplication, 0, 0),
+        ]);
+        let mut inputs = vec![vec![(0, 0), (1, 0)], vec![(0, 2), (1, 0)], vec![(0, -7), (1, 0)]];
+        assert!(multiplication.behaves_like(&mut addition, &inputs));
+        assert!(multiplication.behaves_like(&mut square, &inputs[..2]));
+        inputs.push(vec![(0, 3), (1, 0)]);
+        assert!(!multiplication.behaves_like(&mut square, &inputs));
+    }
+
+    #[test]
+    fn test_stack_top() {
+        let mut args = RuntimeArgs::new();
+        assert_eq!(args.stack_top(), None);
+        args.stack.push(3);
+        args.stack.push(-1);
+        assert_eq!(args.stack_top(), Some(-1));
+        assert_eq!(args.stack.len(), 2);
+    }
+
+    #[test]
+    fn test_run_with_timeout() {
+        let mut runner = Runner::new(vec![
+            Instruction::Nop(),
+            Instruction::Goto("loop"),
+        ]);
+        runner.add_label("loop", 0).unwrap();
+        let timeout = std::time::Duration::from_millis(20);
+        assert_eq!(runner.run_with_timeout(timeout), Err(RuntimeError::Timeout(timeout)));
+        let mut runner = Runner::new(vec![Instruction::AssignAccumulatorValue(0, 1)]);
+        assert_eq!(runner.run_with_timeout(timeout), Ok(()));
+    }
+
+    #[test]
+    fn test_accumulator_snapshot() {
+        let mut args = RuntimeArgs::new();
+        args.set_accumulator(0, 5).unwrap();
+        let snapshot = args.accumulator_snapshot();
+        args.set_accumulator(0, 7).unwrap();
+        args.set_accumulator(1, 2).unwrap();
+        args.restore_accumulators(&snapshot);
+        assert_eq!(args.accumulator_snapshot(), snapshot);
+        assert_eq!(args.accumulators[0].data, Some(5));
+        assert_eq!(args.accumulators[1].data, None);
+    }
+
+    #[test]
+    fn test_potentially_failing() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 10),
+            Instruction::AssignMemoryCellValue("a", 2),
+            Instruction::CalcAccumulatorWithMemoryCell(Operation::Division, 0, "a"),
+            Instruction::CalcAccumulatorWithAccumulator(Operation::Plus, 0, 1),
+            Instruction::Goto("end"),
+        ];
+        let runner = Runner::new(instructions);
+        assert_eq!(runner.potentially_failing(), vec![(2, "division by zero"), (3, "read of uninitialized value"), (4, "label missing")]);
+    }
+
+    #[test]
+    fn test_case_insensitive_labels() {
+        let instructions = vec![
+            Instruction::Goto("Loop"),
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::AssignAccumulatorValue(1, 1),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 2).unwrap();
+        assert_eq!(runner.clone().run(), Err(RuntimeError::LabelMissing(String::from("Loop"))));
+        runner.control_flow.case_insensitive_labels = true;
+        runner.run().unwrap();
+        assert!(runner.runtime_args().accumulators[0].data.is_none());
+        assert_eq!(runner.runtime_args().accumulators[1].data, Some(1));
+    }
+
+    #[test]
+    fn test_link_case_insensitive_labels() {
+        let instructions = vec![
+            Instruction::Goto("Loop"),
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::AssignAccumulatorValue(1, 1),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 2).unwrap();
+        runner.control_flow.case_insensitive_labels = true;
+        runner.link();
+        assert_eq!(runner.linked_indices, vec![Some(2), None, None]);
+        runner.run().unwrap();
+        assert!(runner.runtime_args().accumulators[0].data.is_none());
+        assert_eq!(runner.runtime_args().accumulators[1].data, Some(1));
+    }
+
+    #[test]
+    fn test_explore_bounded() {
+        let instructions = vec![
+            Instruction::CalcAccumulatorWithConstant(Operation::Multiplication, 0, 2),
+        ];
+        let mut runner = Runner::new(instructions);
+        let results: Vec<(i32, Option<i32>)> = runner.explore_bounded(0, 0..3).iter()
+            .map(|(input, state)| (*input, state.accumulators[0].data))
+            .collect();
+        assert_eq!(results, vec![(0, Some(0)), (1, Some(2)), (2, Some(4))]);
+    }
+
+    #[test]
+    fn test_state_hash() {
+        let mut a = RuntimeArgs::new();
+        let mut b = RuntimeArgs::new();
+        a.set_accumulator(0, 3).unwrap();
+        b.set_accumulator(0, 3).unwrap();
+        a.stack.push(1);
+        b.stack.push(1);
+        assert_eq!(a.state_hash(), b.state_hash());
+        b.memory_cells.get_mut("a").unwrap().data = Some(1);
+        assert_ne!(a.state_hash(), b.state_hash());
+    }
+
+    #[test]
+    fn test_annotated_listing() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 0),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 1),
+            Instruction::GotoIfConstant(Comparison::Less, "loop", 0, 3),
+            Instruction::Goto("end"),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 1).unwrap();
+        runner.run_with_limit(7).unwrap();
+        let listing = runner.annotated_listing();
+        let lines: Vec<&str> = listing.lines().collect();
+        assert_eq!(lines[1], "  1 |      3x | a0 := a0 + 1");
+        assert_eq!(lines[3], "  3 |      0x | goto end");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_program_json() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 1),
+            Instruction::GotoIfConstant(Comparison::Less, "loop", 0, 5),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 1).unwrap();
+        runner.run().unwrap();
+        let json = runner.program_to_json();
+        let mut copy = Runner::program_from_json(&json).unwrap();
+        assert_eq!(copy.program_to_json(), json);
+        assert!(copy.runtime_args().accumulators[0].data.is_none());
+        let listing: Vec<String> = copy.instructions.iter().map(|instruction| instruction.to_string()).collect();
+        assert_eq!(listing, vec!["a0 := 1", "a0 := a0 + 1", "if a0 < 5 then goto loop"]);
+        copy.run().unwrap();
+        assert_eq!(copy.runtime_args().accumulators[0].data, Some(5));
+        assert!(Runner::program_from_json("{}").is_err());
+    }
+
+    #[test]
+    fn test_operation_histogram() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::CalcAccumulatorWithConstant(Operation::Multiplication, 0, 3),
+            Instruction::CalcAccumulatorWithAccumulator(Operation::Plus, 0, 0),
+            Instruction::CalcMemoryCellWithMemoryCellConstant(Operation::Multiplication, "a", "b", 2),
+        ];
+        let histogram = Runner::new(instructions).operation_histogram();
+        assert_eq!(histogram.len(), 2);
+        assert_eq!(histogram[&Operation::Multiplication], 2);
+        assert_eq!(histogram[&Operation::Plus], 1);
+    }
+
+    #[test]
+    fn test_path_to() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 0),
+            Instruction::GotoIfConstant(Comparison::More, "end", 0, 3),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 1),
+            Instruction::Goto("loop"),
+            Instruction::AssignAccumulatorValue(1, 0),
+            Instruction::Nop(),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 1).unwrap();
+        runner.add_label("end", 5).unwrap();
+        assert_eq!(runner.path_to(2), Some(vec![0, 1, 2]));
+        assert_eq!(runner.path_to(5), Some(vec![0, 1, 5]));
+        assert_eq!(runner.path_to(4), None);
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let mut args = RuntimeArgs::new_empty();
+        args.add_accumulator();
+        args.add_accumulator();
+        args.add_storage_cell("b");
+        args.add_storage_cell("a");
+        args.set_accumulator(1, -3).unwrap();
+        args.memory_cells.get_mut("b").unwrap().data = Some(7);
+        assert_eq!(args.to_csv(), "name,value\na0,\na1,-3\np(a),\np(b),7\n");
+    }
+
+    #[test]
+    fn test_value_ranges() {
+        let instructions = vec![
+            Instruction::AssignMemoryCellValue("a", 0),
+            Instruction::AssignMemoryCellValue("a", 10),
+            Instruction::AssignMemoryCellValue("a", 5),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.enable_value_ranges();
+        runner.run().unwrap();
+        let value_ranges = runner.value_ranges();
+        assert_eq!(value_ranges.len(), 1);
+        assert_eq!(value_ranges["a"], (0, 10));
+    }
+
+    #[test]
+    fn test_latency_report() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 0),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 1),
+            Instruction::GotoIfConstant(Comparison::Less, "loop", 0, 3),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 1).unwrap();
+        runner.run().unwrap();
+        assert!(runner.latency_report().is_empty());
+        runner.reset();
+        runner.enable_latency_tracking();
+        runner.run().unwrap();
+        let mut counts: Vec<(&str, u64)> = runner.latency_report().iter().map(|(kind, _, _, count)| (*kind, *count)).collect();
+        counts.sort();
+        assert_eq!(counts, vec![("AssignAccumulatorValue", 1), ("CalcAccumulatorWithConstant", 3), ("GotoIfConstant", 3)]);
+        for (_, total, average, count) in runner.latency_report() {
+            assert!(average <= total);
+            assert!(count > 0);
+        }
+        // counts above u32::MAX must not be truncated
+        let count = u32::MAX as u64 + 1;
+        runner.latencies = Some(HashMap::from([("Push", (Duration::from_secs(10), count))]));
+        assert_eq!(runner.latency_report(), vec![("Push", Duration::from_secs(10), Duration::from_nanos(10_000_000_000 / count), count)]);
+    }
+
+    #[test]
+    fn test_find_redundant() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 4),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 0),
+            Instruction::CalcAccumulatorWithConstant(Operation::Multiplication, 0, 2),
+            Instruction::AssignAccumulatorValueFromAccumulator(0, 0),
+            Instruction::AssignMemoryCellValue("a", 6),
+            Instruction::CalcMemoryCellWithMemoryCellConstant(Operation::Division, "a", "a", 1),
+            Instruction::CalcMemoryCellWithMemoryCellConstant(Operation::Division, "a", "b", 1),
+            // a1 and p(b) are empty, these fail or change the value to 0
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 1, 0),
+            Instruction::AssingMemoryCellValueFromMemoryCell("b", "b"),
+            Instruction::ResetState(),
+            Instruction::AssignAccumulatorValueFromAccumulator(0, 0),
+            Instruction::Nop(),
+        ];
+        assert_eq!(Runner::new(instructions).find_redundant(), vec![1, 3, 5, 11]);
+    }
+
+    #[test]
+    fn test_unused_labels() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 0),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 1),
+            Instruction::GotoIfConstant(Comparison::Less, "loop", 0, 3),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("start", 0).unwrap();
+        runner.add_label("loop", 1).unwrap();
+        runner.add_label("check", 2).unwrap();
+        assert_eq!(runner.unused_labels(), vec!["check", "start"]);
+    }
+
+    #[test]
+    fn test_fetch_hook() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::AssignAccumulatorValue(0, 2),
+            Instruction::AssignAccumulatorValue(1, 3),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.set_fetch_hook(Box::new(|index| if index == 1 { 2 } else { index }));
+        runner.run().unwrap();
+        assert_eq!(runner.runtime_args().accumulators[0].data, Some(1));
+        assert_eq!(runner.runtime_args().accumulators[1].data, Some(3));
+        assert_eq!(runner.coverage(), vec![1, 0, 1]);
+    }
+
+    #[test]
+    fn test_fetch_hook_clone() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::AssignAccumulatorValue(0, 2),
+        ];
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let hook_calls = calls.clone();
+        let mut runner = Runner::new(instructions);
+        runner.set_fetch_hook(Box::new(move |index| {
+            hook_calls.borrow_mut().push(index);
+            index
+        }));
+        let mut clone = runner.clone();
+        clone.run().unwrap();
+        assert!(calls.borrow().is_empty());
+        runner.run().unwrap();
+        assert_eq!(*calls.borrow(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::CalcAccumulatorWithConstant(Operation::Multiplication, 0, 2),
+            Instruction::PushConstant(1),
+            Instruction::GotoIfConstant(Comparison::Less, "loop", 0, 100),
+        ];
+        let mut straight = Runner::new(instructions.clone());
+        straight.add_label("loop", 1).unwrap();
+        let mut paused = straight.clone();
+        straight.run().unwrap();
+        paused.run_with_limit(10).unwrap();
+        let mut resumed = Runner::from_checkpoint(paused.checkpoint());
+        resumed.run().unwrap();
+        assert!(resumed.runtime_args().diff_report(straight.runtime_args()).is_empty());
+        assert_eq!(resumed.runtime_args().accumulators[0].data, Some(128));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_checkpoint_json() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::CalcAccumulatorWithConstant(Operation::Multiplication, 0, 2),
+            Instruction::PushConstant(1),
+            Instruction::GotoIfConstant(Comparison::Less, "loop", 0, 100),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 1).unwrap();
+        runner.run_with_limit(10).unwrap();
+        let json = serde_json::to_string(&runner.checkpoint()).unwrap();
+        let checkpoint: crate::runtime::Checkpoint = serde_json::from_str(&json).unwrap();
+        let mut resumed = Runner::from_checkpoint(checkpoint);
+        runner.run().unwrap();
+        resumed.run().unwrap();
+        assert!(resumed.runtime_args().diff_report(runner.runtime_args()).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_runtime_error_to_json() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::Goto("fail"),
+            Instruction::Nop(),
+            Instruction::AssignAccumulatorValueFromMemoryCell(0, "z"),
+            Instruction::Nop(),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("fail", 3).unwrap();
+        let (index, result) = loop {
+            let (index, result) = runner.step_with_index();
+            if result.is_err() {
+                break (index, result);
+            }
+        };
+        let json: serde_json::Value = serde_json::from_str(&result.unwrap_err().to_json(index.unwrap())).unwrap();
+        assert_eq!(json["variant"], "MemoryCellDoesNotExist");
+        assert_eq!(json["instruction_index"], 3);
+        assert_eq!(json["message"], "Memory cell with label z does not exist!");
+    }
+
+    #[test]
+    fn test_referenced_cells() {
+        let instructions = vec![
+            Instruction::AssignMemoryCellValue("c", 1),
+            Instruction::AssignAccumulatorValueFromMemoryCell(0, "a"),
+            Instruction::CalcMemoryCellWithMemoryCells(Operation::Plus, "a", "b", "c"),
+            Instruction::AssignAccumulatorValue(1, 2),
+        ];
+        assert_eq!(Runner::new(instructions).referenced_cells(), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_complexity() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 0),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 1),
+            Instruction::GotoIfConstant(Comparison::Less, "loop", 0, 3),
+            Instruction::GotoIfConstant(Comparison::Equal, "end", 0, 3),
+            Instruction::Nop(),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 1).unwrap();
+        runner.add_label("end", 4).unwrap();
+        assert_eq!(runner.complexity(), ComplexityReport { instructions: 5, branches: 2, loops: 1, max_nesting: 1 });
+    }
+
+    #[test]
+    fn test_complexity_subroutine_defined_earlier() {
+        let instructions = vec![
+            Instruction::Goto("main"),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 1),
+            Instruction::Return(),
+            Instruction::AssignAccumulatorValue(0, 0),
+            Instruction::Call("increment"),
+            Instruction::Call("increment"),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("increment", 1).unwrap();
+        runner.add_label("main", 3).unwrap();
+        assert_eq!(runner.complexity(), ComplexityReport { instructions: 6, branches: 0, loops: 0, max_nesting: 0 });
+    }
+
+    #[test]
+    fn test_potentially_failing_constant_division_by_zero() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 4),
+            Instruction::CalcAccumulatorWithConstant(Operation::Division, 0, 0),
+            Instruction::CalcAccumulatorWithConstant(Operation::Division, 0, 2),
+            Instruction::CalcAccumulatorWithConstant(Operation::Minus, 0, 0),
+            Instruction::GotoIfDivisible(0, 0, "end"),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("end", 4).unwrap();
+        assert_eq!(runner.potentially_failing(), vec![(1, "division by constant zero"), (4, "division by constant zero")]);
+    }
+
+    #[test]
+    fn test_run_logged() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 5),
+            Instruction::AssignMemoryCellValueFromAccumulator("a", 0),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 3),
+            Instruction::Nop(),
+        ];
+        let mut runner = Runner::new(instructions);
+        let mut log = Vec::new();
+        runner.run_logged(&mut log).unwrap();
+        let expected = "a0 := 5  ; a0: None -> 5\n\
+            p(a) := a0  ; p(a): None -> 5\n\
+            a0 := a0 + 3  ; a0: 5 -> 8\n\
+            nop\n";
+        assert_eq!(String::from_utf8(log).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_run_logged_fetch_hook() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::AssignAccumulatorValue(0, 2),
+            Instruction::AssignAccumulatorValue(1, 3),
+        ];
+        let mut runner = Runner::new(instructions.clone());
+        runner.set_fetch_hook(Box::new(|index| if index == 1 { 2 } else { index }));
+        assert_eq!(runner.clone().step(), Ok(Some(0)));
+        let mut log = Vec::new();
+        runner.run_logged(&mut log).unwrap();
+        assert_eq!(String::from_utf8(log).unwrap(), "a0 := 1  ; a0: None -> 1\na1 := 3  ; a1: None -> 3\n");
+        // the hook ends the program instead of running instruction 1
+        let mut runner = Runner::new(instructions);
+        runner.set_fetch_hook(Box::new(|index| if index == 1 { 5 } else { index }));
+        let mut log = Vec::new();
+        runner.run_logged(&mut log).unwrap();
+        assert_eq!(String::from_utf8(log).unwrap(), "a0 := 1  ; a0: None -> 1\n");
+        assert_eq!(runner.step(), Ok(None));
+    }
+
+    #[test]
+    fn test_validate_programs() {
+        let valid = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::AssignMemoryCellValueFromAccumulator("a", 0),
+        ];
+        let invalid = vec![
+            Instruction::AssignAccumulatorValue(10, 1),
+            Instruction::AssignMemoryCellValueFromAccumulator("z", 10),
+            Instruction::Goto("end"),
+        ];
+        let results = validate_programs(&[valid, invalid]);
+        assert_eq!(results[0], Ok(()));
+        assert_eq!(results[1], Err(vec![
+            RuntimeError::AccumulatorDoesNotExist(10),
+            RuntimeError::MemoryCellDoesNotExist(String::from("z")),
+            RuntimeError::LabelMissing(String::from("end")),
+        ]));
+    }
+
+    #[test]
+    fn test_check_accumulator_bounds() {
+        let mut args = RuntimeArgs::new_empty();
+        for _ in 0..3 {
+            args.add_accumulator();
+        }
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::CalcAccumulatorWithAccumulator(Operation::Plus, 5, 5),
+            Instruction::AssignAccumulatorValueFromAccumulator(2, 3),
+        ];
+        let runner = Runner::new_custom(instructions, args);
+        assert_eq!(runner.check_accumulator_bounds(), vec![(1, 5), (2, 3)]);
+    }
+
+    #[test]
+    fn test_run_until_stable() {
+        let mut args = RuntimeArgs::new();
+        args.set_accumulator(0, 20).unwrap();
+        let instructions = vec![
+            Instruction::CalcAccumulatorWithConstant(Operation::Division, 0, 2),
+        ];
+        let mut runner = Runner::new_custom(instructions, args);
+        assert_eq!(runner.clone().run_until_stable(3), Ok(None));
+        assert_eq!(runner.run_until_stable(10), Ok(Some(5)));
+        assert_eq!(runner.runtime_args().accumulators[0].data, Some(0));
+    }
+
+    #[test]
+    fn test_stack_balance() {
+        let balanced = Runner::new(vec![
+            Instruction::PushConstant(1),
+            Instruction::PushConstant(2),
+            Instruction::Pop(),
+            Instruction::PopN(1),
+        ]);
+        assert_eq!(balanced.stack_balance(), Ok(0));
+        let left_over = Runner::new(vec![
+            Instruction::PushConstant(1),
+            Instruction::PushConstant(2),
+            Instruction::Pop(),
+        ]);
+        assert_eq!(left_over.stack_balance(), Ok(1));
+        let reset = Runner::new(vec![
+            Instruction::PushConstant(1),
+            Instruction::ResetState(),
+            Instruction::PushConstant(2),
+            Instruction::Pop(),
+        ]);
+        assert_eq!(reset.stack_balance(), Ok(0));
+        let large_pop = Runner::new(vec![
+            Instruction::PushConstant(1),
+            Instruction::PopN(usize::MAX),
+        ]);
+        assert!(large_pop.stack_balance().is_err());
+        let negative = Runner::new(vec![
+            Instruction::PushConstant(1),
+            Instruction::PopN(2),
+            Instruction::PushConstant(1),
+        ]);
+        assert_eq!(negative.stack_balance(), Err(String::from("Stack could be empty when instruction 1 (pop 2 values) pops a value!")));
+    }
+
+    #[test]
+    fn test_diff_traces() {
+        let program = |step: i32| vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, step),
+            Instruction::GotoIfConstant(Comparison::Less, "loop", 0, 5),
+        ];
+        let mut buggy = Runner::new(program(2));
+        let mut fixed = Runner::new(program(1));
+        for runner in [&mut buggy, &mut fixed] {
+            runner.add_label("loop", 1).unwrap();
+            runner.enable_trace();
+            runner.run().unwrap();
+        }
+        assert_eq!(diff_traces(buggy.trace(), fixed.trace()), Some(1));
+        assert_eq!(diff_traces(fixed.trace(), fixed.trace()), None);
+        assert_eq!(diff_traces(&fixed.trace()[..3].to_vec(), fixed.trace()), Some(3));
+    }
+
+    #[test]
+    fn test_verify_trace() {
+        let instructions = vec![
+            Instruction::AssignAccumulatorValue(0, 1),
+            Instruction::CalcAccumulatorWithConstant(Operation::Plus, 0, 1),
+            Instruction::GotoIfConstant(Comparison::Less, "loop", 0, 5),
+        ];
+        let mut runner = Runner::new(instructions);
+        runner.add_label("loop", 1).unwrap();
+        runner.enable_trace();
+        runner.run().unwrap();
+        let trace = runner.trace().clone();
+        assert_eq!(runner.verify_trace(&trace), Ok(()));
+        assert_eq!(runner.trace(), &trace);
+        let mut changed = trace.clone();
+        changed[4].changes = String::from("a0: 3 -> 4");
+        assert_eq!(runner.verify_trace(&changed), Err(4));
+        assert_eq!(runner.verify_trace(&trace[..3].to_vec()), Err(3));
+    }
+
+    #[test]
+    fn test_jump_table() {
+        // a1 selects the entry of the jump table that is stored in p(t0) and p(t1)
+        let instructions = vec![
+            Instruction::LoadLabelAddress(0, "first"),
+            Instruction::AssignMemoryCellValueFromAccumulator("t0", 0),
+            Instruction::LoadLabelAddress(0, "second"),
+            Instruction::AssignMemoryCellValueFromAccumulator("t1", 0),
+            Instruction::AssignAccumulatorValueFromMemoryCell(0, "t0"),
+            Instruction::GotoIfConstant(Comparison::Equal, "dispatch", 1, 0),
+            Instruction::AssignAccumulatorValueFromMemoryCell(0, "t1"),
+            Instruction::GotoAccumulator(0),
+            Instruction::AssignAccumulatorValue(2, 10),
+            Instruction::Goto("end"),
+            Instruction::AssignAccumulatorValue(2, 20),
+            Instruction::Nop(),
+        ];
+        let mut args = RuntimeArgs::new_empty();
+        for _ in 0..3 {
+            args.add_accumulator();
+        }
+        args.add_storage_cell("t0");
+        args.add_storage_cell("t1");
+        let mut runner = Runner::new_custom(instructions, args);
+        runner.add_label("dispatch", 7).unwrap();
+        runner.add_label("first", 8).unwrap();
+        runner.add_label("second", 10).unwrap();
+        runner.add_label("end", 11).unwrap();
+        assert!(runner.unused_labels().is_empty());
+        assert_eq!(runner.validate(), Ok(()));
+        let results = runner.run_batch(&[vec![(1, 0)], vec![(1, 1)]]);
+        assert_eq!(results[0].as_ref().unwrap().accumulators[2].data, Some(10));
+        assert_eq!(results[1].as_ref().unwrap().accumulators[2].data, Some(20));
+        // a0 does not point to a label
+        let mut args = RuntimeArgs::new_empty();
+        args.add_accumulator();
+        let mut runner = Runner::new_custom(vec![Instruction::AssignAccumulatorValue(0, 5), Instruction::GotoAccumulator(0)], args.clone());
+        assert_eq!(runner.run(), Err(RuntimeError::InvalidIndex(5)));
+        let mut runner = Runner::new_custom(vec![Instruction::LoadLabelAddress(0, "missing")], args);
+        assert_eq!(runner.validate(), Err(vec![RuntimeError::LabelMissing(String::from("missing"))]));
+        assert_eq!(runner.potentially_failing(), vec![(0, "label missing")]);
+        assert_eq!(runner.run(), Err(RuntimeError::LabelMissing(String::from("missing"))));
+    }
+}